@@ -0,0 +1,255 @@
+// line based protocol between the engine and a solver running as a subprocess
+//
+// engine to bot:
+//   mastermind <positions> <colors>   first line, the bot answers "ready"
+//   new                               a new game starts
+//   go                                the bot answers with "guess <code>"
+//   feedback <black> <white>          the last guess was wrong
+//   solved                            the last guess was the secret code
+//   quit                              the bot exits
+//
+// bot to engine:
+//   ready                             the bot can play on this board
+//   guess <code>                      a code given as digits, e.g. "guess 0123"
+//   info <text>                       ignored by the engine, e.g. for debugging
+//   error <message>                   the bot gives up
+use crate::colors::Colors;
+use crate::evaluation::Evaluation;
+use crate::mastermind::{GuessStatus, Mastermind};
+use crate::mastermind_state::{
+    get_string_from_guess, parse_guess, MastermindState, Values, NUM_ELEMENTS,
+};
+use crate::solver::StrategyFn;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Write};
+use std::process::{Command, Stdio};
+
+// a bot which never finds the code must not block the engine forever
+const MAX_GUESSES: usize = 100;
+
+fn protocol_error(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+// next line of the bot which is not an info line
+fn read_message<T: BufRead>(input: &mut T) -> Result<String, Error> {
+    loop {
+        let mut line = String::new();
+        if 0 == input.read_line(&mut line)? {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "bot exited"));
+        }
+        let line = line.trim();
+        if !line.is_empty() && !line.starts_with("info") {
+            return Ok(String::from(line));
+        }
+    }
+}
+
+// lets the bot play one game, returns its last guess
+pub fn play_bot<T: BufRead, W: Write>(
+    mm: &mut Mastermind,
+    mut input: T,
+    mut output: W,
+) -> Result<Values, Error> {
+    writeln!(output, "mastermind {} {}", NUM_ELEMENTS, Colors::len())?;
+    output.flush()?;
+    let message = read_message(&mut input)?;
+    if "ready" != message {
+        return Err(protocol_error(format!("expected ready, got '{}'", message)));
+    }
+    writeln!(output, "new")?;
+    for _ in 0..MAX_GUESSES {
+        writeln!(output, "go")?;
+        output.flush()?;
+        let message = read_message(&mut input)?;
+        let guess = message.strip_prefix("guess ").and_then(parse_guess);
+        let guess =
+            guess.ok_or_else(|| protocol_error(format!("expected guess, got '{}'", message)))?;
+        match mm.guess(guess) {
+            GuessStatus::Success => {
+                writeln!(output, "solved")?;
+                writeln!(output, "quit")?;
+                output.flush()?;
+                return Ok(guess);
+            }
+            GuessStatus::Incorrect(e) => writeln!(
+                output,
+                "feedback {} {}",
+                e.get_correct_match(),
+                e.get_color_present()
+            )?,
+            GuessStatus::Rejected => {
+                return Err(protocol_error(format!(
+                    "guess {} is not allowed",
+                    get_string_from_guess(&guess)
+                )))
+            }
+        }
+    }
+    Err(protocol_error(format!(
+        "bot did not find the code in {} guesses",
+        MAX_GUESSES
+    )))
+}
+
+// starts the command as a subprocess and lets it play
+pub fn solve_with_bot(mm: &mut Mastermind, command: &[String]) -> Result<Values, Error> {
+    let program = command
+        .first()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "no bot command given"))?;
+    let mut child = Command::new(program)
+        .args(&command[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let input = BufReader::new(child.stdout.take().unwrap());
+    let output = child.stdin.take().unwrap();
+    let result = play_bot(mm, input, output);
+    if result.is_err() {
+        let _ = child.kill();
+    }
+    child.wait()?;
+    result
+}
+
+fn parse_feedback(line: &str) -> Option<Evaluation> {
+    let mut words = line.split_whitespace();
+    let correct_match = words.next()?.parse::<u8>().ok()?;
+    let color_present = words.next()?.parse::<u8>().ok()?;
+    Some(Evaluation::new(correct_match, color_present))
+}
+
+// the bot side of the protocol, the strategy picks every guess from the history
+pub fn run_bot<T: BufRead, W: Write>(
+    input: T,
+    mut output: W,
+    strategy: StrategyFn,
+) -> Result<(), Error> {
+    let mut history: Vec<MastermindState> = Vec::new();
+    let mut last_guess = None;
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim();
+        if let Some(board) = line.strip_prefix("mastermind ") {
+            if format!("{} {}", NUM_ELEMENTS, Colors::len()) == board.trim() {
+                writeln!(output, "ready")?;
+            } else {
+                writeln!(output, "error unsupported board {}", board)?;
+            }
+        } else if "new" == line {
+            history.clear();
+            last_guess = None;
+        } else if "go" == line {
+            match strategy(&history) {
+                Some(guess) => {
+                    last_guess = Some(guess);
+                    writeln!(output, "guess {}", get_string_from_guess(&guess))?;
+                }
+                None => writeln!(output, "error no code fits the feedback")?,
+            }
+        } else if let Some(feedback) = line.strip_prefix("feedback ") {
+            match (last_guess, parse_feedback(feedback)) {
+                (Some(guess), Some(eval)) => history.push(MastermindState::new(guess, eval)),
+                _ => writeln!(output, "info ignored '{}'", line)?,
+            }
+        } else if "quit" == line {
+            break;
+        } else if "solved" != line {
+            writeln!(output, "info unknown command '{}'", line)?;
+        }
+        output.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bot::{play_bot, run_bot};
+    use crate::colors::Colors;
+    use crate::evaluation::Evaluation;
+    use crate::mastermind::Mastermind;
+    use crate::mastermind_state::{get_string_from_guess, MastermindState, Values};
+    use crate::state_of_the_art_solver;
+    use std::io::{pipe, BufReader};
+    use std::thread;
+
+    fn answers(requests: &str) -> String {
+        let mut output = Vec::new();
+        run_bot(
+            requests.as_bytes(),
+            &mut output,
+            state_of_the_art_solver::next_guess,
+        )
+        .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn bot_answers_the_engine() {
+        assert_eq!(
+            "ready\nguess 0011\nguess 0011\n",
+            answers("mastermind 4 6\nnew\ngo\nnew\ngo\nquit\ngo\n")
+        );
+        assert_eq!("error unsupported board 5 8\n", answers("mastermind 5 8\n"));
+        assert_eq!("info unknown command 'hello'\n", answers("hello\n"));
+    }
+
+    #[test]
+    fn bot_uses_the_feedback() {
+        let output = answers("mastermind 4 6\nnew\ngo\nfeedback 1 0\ngo\nsolved\nquit\n");
+        let history = [MastermindState::new(
+            [Colors::Red, Colors::Red, Colors::Green, Colors::Green],
+            Evaluation::new(1, 0),
+        )];
+        let second = state_of_the_art_solver::next_guess(&history).unwrap();
+        assert_eq!(
+            format!(
+                "ready\nguess 0011\nguess {}\n",
+                get_string_from_guess(&second)
+            ),
+            output
+        );
+    }
+
+    #[test]
+    fn bot_gives_up_on_contradictory_feedback() {
+        let output = answers("mastermind 4 6\nnew\ngo\nfeedback 4 0\ngo\nfeedback 0 0\ngo\nquit\n");
+        assert!(output.ends_with("error no code fits the feedback\n"));
+    }
+
+    fn play_with_bot(values: Values) -> (Mastermind, Values) {
+        let (engine_input, bot_output) = pipe().unwrap();
+        let (bot_input, engine_output) = pipe().unwrap();
+        let bot = thread::spawn(move || {
+            run_bot(
+                BufReader::new(bot_input),
+                bot_output,
+                state_of_the_art_solver::next_guess,
+            )
+        });
+        let mut mm = Mastermind::new_with_state(values);
+        let solution = play_bot(&mut mm, BufReader::new(engine_input), engine_output).unwrap();
+        bot.join().unwrap().unwrap();
+        (mm, solution)
+    }
+
+    #[test]
+    fn engine_plays_with_the_bot() {
+        let values = [Colors::Yellow, Colors::White, Colors::Red, Colors::Yellow];
+        let (mm, solution) = play_with_bot(values);
+        assert_eq!(values, solution);
+
+        let mut expected = Mastermind::new_with_state(values);
+        state_of_the_art_solver::solve(&mut expected);
+        assert_eq!(expected.get_guesses(), mm.get_guesses());
+    }
+
+    #[test]
+    fn engine_rejects_invalid_guesses() {
+        let mut mm = Mastermind::new_with_state([Colors::Red; 4]);
+        let error = play_bot(&mut mm, "ready\nguess 01\n".as_bytes(), Vec::new()).unwrap_err();
+        assert!(error.to_string().contains("guess 01"));
+        let error = play_bot(&mut mm, "hello\n".as_bytes(), Vec::new()).unwrap_err();
+        assert!(error.to_string().contains("expected ready"));
+        assert!(play_bot(&mut mm, "ready\n".as_bytes(), Vec::new()).is_err());
+    }
+}
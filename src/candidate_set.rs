@@ -0,0 +1,145 @@
+use crate::code::{get_num_codes, Code};
+
+// set of codes with one bit per code number
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidateSet {
+    bits: Vec<u64>,
+}
+
+impl CandidateSet {
+    pub fn new() -> Self {
+        CandidateSet {
+            bits: vec![0; get_num_codes().div_ceil(64)],
+        }
+    }
+
+    pub fn new_full() -> Self {
+        let mut set = CandidateSet::new();
+        for code in 0..get_num_codes() {
+            set.insert(code as Code);
+        }
+        set
+    }
+
+    pub fn from_codes(codes: &[Code]) -> Self {
+        let mut set = CandidateSet::new();
+        for code in codes {
+            set.insert(*code);
+        }
+        set
+    }
+
+    pub fn insert(&mut self, code: Code) {
+        self.bits[code as usize / 64] |= 1 << (code % 64);
+    }
+
+    pub fn remove(&mut self, code: Code) {
+        self.bits[code as usize / 64] &= !(1 << (code % 64));
+    }
+
+    pub fn contains(&self, code: Code) -> bool {
+        0 != self.bits[code as usize / 64] & (1 << (code % 64))
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|word| 0 == *word)
+    }
+
+    // codes of self which are not in other
+    pub fn difference(&self, other: &CandidateSet) -> CandidateSet {
+        CandidateSet {
+            bits: self
+                .bits
+                .iter()
+                .zip(other.bits.iter())
+                .map(|(a, b)| a & !b)
+                .collect(),
+        }
+    }
+
+    // keeps only the codes for which keep returns true
+    pub fn retain<F: FnMut(Code) -> bool>(&mut self, mut keep: F) {
+        for code in self.iter().collect::<Vec<Code>>() {
+            if !keep(code) {
+                self.remove(code);
+            }
+        }
+    }
+
+    // codes in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = Code> + '_ {
+        self.bits.iter().enumerate().flat_map(|(i, word)| {
+            let mut rest = *word;
+            std::iter::from_fn(move || {
+                if 0 == rest {
+                    return None;
+                }
+                let bit = rest.trailing_zeros();
+                rest &= rest - 1;
+                Some((i * 64) as Code + bit as Code)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::candidate_set::CandidateSet;
+    use crate::code::{get_num_codes, Code};
+
+    #[test]
+    fn new_is_empty() {
+        let set = CandidateSet::new();
+        assert!(set.is_empty());
+        assert_eq!(0, set.len());
+        assert_eq!(None, set.iter().next());
+    }
+
+    #[test]
+    fn new_full_contains_every_code() {
+        let set = CandidateSet::new_full();
+        assert_eq!(get_num_codes(), set.len());
+        assert!(set.contains(0));
+        assert!(set.contains(get_num_codes() as Code - 1));
+        assert_eq!(
+            (0..get_num_codes() as Code).collect::<Vec<Code>>(),
+            set.iter().collect::<Vec<Code>>()
+        );
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut set = CandidateSet::new();
+        set.insert(63);
+        set.insert(64);
+        set.insert(1000);
+        assert_eq!(3, set.len());
+        assert!(set.contains(64));
+        set.remove(64);
+        assert!(!set.contains(64));
+        assert_eq!(vec![63, 1000], set.iter().collect::<Vec<Code>>());
+    }
+
+    #[test]
+    fn difference() {
+        let a = CandidateSet::from_codes(&[1, 5, 70, 700]);
+        let b = CandidateSet::from_codes(&[5, 6, 700, 1295]);
+        assert_eq!(vec![1, 70], a.difference(&b).iter().collect::<Vec<Code>>());
+    }
+
+    #[test]
+    fn retain() {
+        let mut set = CandidateSet::new_full();
+        set.retain(|code| 0 == code % 100);
+        assert_eq!(13, set.len());
+        assert!(set.contains(1200));
+        assert!(!set.contains(1201));
+    }
+}
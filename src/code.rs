@@ -0,0 +1,76 @@
+use crate::colors::Colors;
+use crate::evaluation::Evaluation;
+use crate::mastermind_state::{Values, NUM_ELEMENTS};
+
+// a code as number in base Colors::len(), the first position is the most significant digit
+pub type Code = u16;
+
+pub const NUM_EVALUATIONS: usize = (NUM_ELEMENTS + 1) * (NUM_ELEMENTS + 1);
+
+pub fn get_num_codes() -> usize {
+    (Colors::len() as usize).pow(NUM_ELEMENTS as u32)
+}
+
+pub fn pack(values: &Values) -> Code {
+    values.iter().fold(0, |code, val| {
+        code * Code::from(Colors::len()) + *val as Code
+    })
+}
+
+pub fn unpack(code: Code) -> Values {
+    let mut values: Values = [Colors::Red; NUM_ELEMENTS];
+    let mut rest = code;
+    for val in values.iter_mut().rev() {
+        *val = Colors::from((rest % Code::from(Colors::len())) as u8);
+        rest /= Code::from(Colors::len());
+    }
+    values
+}
+
+pub fn pack_evaluation(eval: &Evaluation) -> u8 {
+    eval.get_correct_match() * (NUM_ELEMENTS as u8 + 1) + eval.get_color_present()
+}
+
+pub fn unpack_evaluation(index: u8) -> Evaluation {
+    Evaluation::new(
+        index / (NUM_ELEMENTS as u8 + 1),
+        index % (NUM_ELEMENTS as u8 + 1),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::code::{
+        get_num_codes, pack, pack_evaluation, unpack, unpack_evaluation, NUM_EVALUATIONS,
+    };
+    use crate::colors::Colors;
+    use crate::evaluation::Evaluation;
+
+    #[test]
+    fn pack_and_unpack() {
+        assert_eq!(0, pack(&[Colors::Red; 4]));
+        assert_eq!(
+            1,
+            pack(&[Colors::Red, Colors::Red, Colors::Red, Colors::Green])
+        );
+        assert_eq!(1295, pack(&[Colors::Black; 4]));
+        for code in 0..get_num_codes() as u16 {
+            assert_eq!(code, pack(&unpack(code)));
+        }
+    }
+
+    #[test]
+    fn pack_and_unpack_evaluation() {
+        assert_eq!(0, pack_evaluation(&Evaluation::new(0, 0)));
+        assert_eq!(
+            NUM_EVALUATIONS - 1,
+            pack_evaluation(&Evaluation::new(4, 4)) as usize
+        );
+        for correct_match in 0..5 {
+            for color_present in 0..5 - correct_match {
+                let eval = Evaluation::new(correct_match, color_present);
+                assert_eq!(eval, unpack_evaluation(pack_evaluation(&eval)));
+            }
+        }
+    }
+}
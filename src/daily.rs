@@ -0,0 +1,118 @@
+use crate::code::{get_num_codes, unpack, Code};
+use crate::mastermind_state::{MastermindState, Values, NUM_ELEMENTS};
+use crate::stats::SECONDS_PER_DAY;
+use crate::util::SeededRandom;
+use std::env;
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// days since 1970-01-01 in UTC, so everyone gets the same puzzle at the same time
+pub fn get_today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs() / SECONDS_PER_DAY)
+}
+
+// the salt given on the command line, $MASTERMIND_DAILY_SALT or "mastermind"
+pub fn get_salt(arg: Option<&String>) -> String {
+    match arg {
+        Some(salt) => salt.clone(),
+        None => env::var("MASTERMIND_DAILY_SALT").unwrap_or_else(|_| String::from("mastermind")),
+    }
+}
+
+// YYYY-MM-DD of a day since 1970-01-01
+pub fn get_date_string(day: u64) -> String {
+    // shifted to start with March, so the leap day is the last day of the year
+    let days = day as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day_of_month = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day_of_month)
+}
+
+// the same day and salt always give the same secret
+pub fn get_daily_secret(day: u64, salt: &str) -> Values {
+    // FNV-1a of the salt
+    let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+    for byte in salt.bytes() {
+        hash = (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3);
+    }
+    let mut random = SeededRandom::new(hash ^ day);
+    unpack(random.next_usize(get_num_codes()) as Code)
+}
+
+// shows the feedback of every guess but none of the colors, in plain text which can be pasted anywhere
+pub fn get_share_grid(day: u64, guesses: &[MastermindState]) -> String {
+    let mut grid = format!(
+        "Mastermind daily {} {} guesses\n",
+        get_date_string(day),
+        guesses.len()
+    );
+    for state in guesses {
+        let eval = state.get_evaluation();
+        let correct_match = eval.get_correct_match() as usize;
+        let color_present = eval.get_color_present() as usize;
+        writeln!(
+            grid,
+            "{}{}{}",
+            "●".repeat(correct_match),
+            "○".repeat(color_present),
+            "_".repeat(NUM_ELEMENTS - correct_match - color_present)
+        )
+        .unwrap();
+    }
+    grid
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::Colors;
+    use crate::daily::{get_daily_secret, get_date_string, get_share_grid};
+    use crate::evaluation::Evaluation;
+    use crate::mastermind_state::MastermindState;
+
+    #[test]
+    fn dates() {
+        assert_eq!("1970-01-01", get_date_string(0));
+        assert_eq!("2000-02-29", get_date_string(11_016));
+        assert_eq!("2000-03-01", get_date_string(11_017));
+        assert_eq!("2024-01-01", get_date_string(19_723));
+        assert_eq!("2026-10-19", get_date_string(20_745));
+    }
+
+    #[test]
+    fn secret_depends_on_day_and_salt() {
+        assert_eq!(
+            get_daily_secret(20_745, "team"),
+            get_daily_secret(20_745, "team")
+        );
+        let days: Vec<_> = (0..10)
+            .map(|day| get_daily_secret(20_745 + day, "team"))
+            .collect();
+        assert!(days.iter().any(|secret| *secret != days[0]));
+        let salts: Vec<_> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|salt| get_daily_secret(20_745, salt))
+            .collect();
+        assert!(salts.iter().any(|secret| *secret != salts[0]));
+    }
+
+    #[test]
+    fn share_grid_hides_the_colors() {
+        let guesses = [
+            MastermindState::new([Colors::Red; 4], Evaluation::new(1, 2)),
+            MastermindState::new([Colors::Green; 4], Evaluation::new(4, 0)),
+        ];
+        assert_eq!(
+            "Mastermind daily 2026-10-19 2 guesses\n●○○_\n●●●●\n",
+            get_share_grid(20_745, &guesses)
+        );
+    }
+}
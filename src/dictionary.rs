@@ -0,0 +1,118 @@
+use crate::colors::Colors;
+use crate::mastermind_state::{Values, NUM_ELEMENTS};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Error, ErrorKind};
+
+// letters a, b, c, ... stand for the colors 0, 1, 2, ... so "face" or "bead" are valid codes
+pub fn parse_word(word: &str) -> Option<Values> {
+    let word = word.trim().to_ascii_lowercase();
+    if NUM_ELEMENTS != word.len() {
+        return None;
+    }
+    let mut values: Values = [Colors::Red; NUM_ELEMENTS];
+    for (val, c) in values.iter_mut().zip(word.bytes()) {
+        if c < b'a' || c >= b'a' + Colors::len() {
+            return None;
+        }
+        *val = Colors::from(c - b'a');
+    }
+    Some(values)
+}
+
+// one word per line, empty lines and lines starting with '#' are skipped,
+// the board is fixed, so a word which is not a code of it makes the whole file invalid
+pub fn read_dictionary<T: BufRead>(input: T) -> Result<Vec<Values>, Error> {
+    let mut dictionary = Vec::new();
+    let mut seen = HashSet::new();
+    for (number, line) in input.lines().enumerate() {
+        let line = line?;
+        let word = line.trim();
+        if word.is_empty() || word.starts_with('#') {
+            continue;
+        }
+        let values = parse_word(word).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "'{}' in line {} is not a code, only words of {} letters from a to {} fit the board",
+                    word,
+                    number + 1,
+                    NUM_ELEMENTS,
+                    (b'a' + Colors::len() - 1) as char
+                ),
+            )
+        })?;
+        if seen.insert(values) {
+            dictionary.push(values);
+        }
+    }
+    if dictionary.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "dictionary has no words",
+        ));
+    }
+    Ok(dictionary)
+}
+
+pub fn load_dictionary(path: &str) -> Result<Vec<Values>, Error> {
+    read_dictionary(BufReader::new(File::open(path)?))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::Colors;
+    use crate::dictionary::{load_dictionary, parse_word, read_dictionary};
+    use std::io::ErrorKind;
+
+    #[test]
+    fn parse_word_maps_letters_to_colors() {
+        assert_eq!(
+            Some([Colors::White, Colors::Red, Colors::Blue, Colors::White]),
+            parse_word("EaCe")
+        );
+        assert_eq!(
+            Some([Colors::Black, Colors::Red, Colors::Blue, Colors::White]),
+            parse_word("face\n")
+        );
+    }
+
+    #[test]
+    fn parse_word_rejects_invalid_words() {
+        assert_eq!(None, parse_word("fac"));
+        assert_eq!(None, parse_word("faces"));
+        assert_eq!(None, parse_word("fact"));
+        assert_eq!(None, parse_word("0123"));
+    }
+
+    #[test]
+    fn read_dictionary_skips_comments_and_duplicates() {
+        let input = "# words\nface\n\nbead\nface\n";
+        let dictionary = read_dictionary(input.as_bytes()).unwrap();
+        assert_eq!(2, dictionary.len());
+        assert_eq!(parse_word("bead"), Some(dictionary[1]));
+    }
+
+    #[test]
+    fn read_dictionary_rejects_words_which_do_not_fit() {
+        let error = read_dictionary("face\n# more\nhouse\ncafe\n".as_bytes()).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, error.kind());
+        assert_eq!(
+            "'house' in line 3 is not a code, only words of 4 letters from a to f fit the board",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn read_empty_dictionary_fails() {
+        let error = read_dictionary("# nothing\n".as_bytes()).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, error.kind());
+    }
+
+    #[test]
+    fn load_missing_dictionary_fails() {
+        let error = load_dictionary("/nonexistent/words.txt").unwrap_err();
+        assert_eq!(ErrorKind::NotFound, error.kind());
+    }
+}
@@ -0,0 +1,290 @@
+use crate::code::{get_num_codes, pack, unpack, Code};
+use crate::colors::Colors;
+use crate::mastermind_state::{MastermindState, Values, NUM_ELEMENTS};
+use crate::puzzle::get_candidates;
+use crate::scoring_rule::{Classic, ScoringRule};
+use std::fmt::{Display, Error, Formatter};
+
+// deeper case splits are too slow and no human does them in their head
+const MAX_SPLIT_DEPTH: usize = 2;
+
+// bit set of the colors still possible in every position
+type Domains = [u8; NUM_ELEMENTS];
+
+// how a human would have to reason to find the code
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rating {
+    pub rows: usize,
+    // codes consistent with every row, a fair puzzle has exactly one
+    pub candidates: usize,
+    // rounds over all rows until no row rules out another color
+    pub propagation_steps: usize,
+    // nested assumptions needed, None if even the deepest were not enough
+    pub split_depth: Option<usize>,
+    // colors ruled out by an assumption leading to a contradiction
+    pub splits: usize,
+}
+
+fn fits(domains: &Domains, values: &Values) -> bool {
+    values
+        .iter()
+        .zip(domains.iter())
+        .all(|(value, domain)| 0 != domain & (1 << *value as u8))
+}
+
+fn is_solved(domains: &Domains) -> bool {
+    domains.iter().all(|domain| 1 == domain.count_ones())
+}
+
+// keeps only colors which some code matching the row can have, looking at one row at a time
+fn propagate(matching: &[Vec<Values>], mut domains: Domains) -> Option<(Domains, usize)> {
+    let mut steps = 0;
+    loop {
+        let before = domains;
+        for codes in matching {
+            let mut supported: Domains = [0; NUM_ELEMENTS];
+            for values in codes.iter().filter(|values| fits(&domains, values)) {
+                for (support, value) in supported.iter_mut().zip(values.iter()) {
+                    *support |= 1 << *value as u8;
+                }
+            }
+            for (domain, support) in domains.iter_mut().zip(supported.iter()) {
+                *domain &= support;
+            }
+            if domains.contains(&0) {
+                return None;
+            }
+        }
+        if before == domains {
+            return Some((domains, steps));
+        }
+        steps += 1;
+    }
+}
+
+// propagation plus assumptions up to the depth, None for a contradiction
+fn reduce(
+    matching: &[Vec<Values>],
+    domains: Domains,
+    depth: usize,
+    splits: &mut usize,
+) -> Option<Domains> {
+    let (mut domains, _) = propagate(matching, domains)?;
+    if 0 == depth {
+        return Some(domains);
+    }
+    let mut changed = true;
+    while changed && !is_solved(&domains) {
+        changed = false;
+        for position in 0..NUM_ELEMENTS {
+            for color in 0..Colors::len() {
+                let bit = 1 << color;
+                if 0 == domains[position] & bit || 1 == domains[position].count_ones() {
+                    continue;
+                }
+                let mut assumed = domains;
+                assumed[position] = bit;
+                if reduce(matching, assumed, depth - 1, &mut 0).is_none() {
+                    *splits += 1;
+                    domains[position] &= !bit;
+                    domains = propagate(matching, domains)?.0;
+                    changed = true;
+                }
+            }
+        }
+    }
+    Some(domains)
+}
+
+pub fn rate(rows: &[MastermindState]) -> Rating {
+    // the codes matching each row on its own
+    let matching: Vec<Vec<Values>> = rows
+        .iter()
+        .map(|row| {
+            let guess = pack(&row.get_values());
+            (0..get_num_codes() as Code)
+                .filter(|code| Classic.score_code(*code, guess) == row.get_evaluation())
+                .map(unpack)
+                .collect()
+        })
+        .collect();
+    let full: Domains = [(1 << Colors::len()) - 1; NUM_ELEMENTS];
+    let candidates = get_candidates(rows).len();
+    let propagation_steps = propagate(&matching, full).map_or(0, |(_, steps)| steps);
+    let mut rating = Rating {
+        rows: rows.len(),
+        candidates,
+        propagation_steps,
+        split_depth: None,
+        splits: 0,
+    };
+    if 1 != candidates {
+        return rating;
+    }
+    for depth in 0..=MAX_SPLIT_DEPTH {
+        let mut splits = 0;
+        if reduce(&matching, full, depth, &mut splits).is_some_and(|d| is_solved(&d)) {
+            rating.split_depth = Some(depth);
+            rating.splits = splits;
+            break;
+        }
+    }
+    rating
+}
+
+impl Rating {
+    // grows with every step a human has to take, assumptions count most
+    pub fn get_score(&self) -> usize {
+        let splitting = match self.split_depth {
+            Some(depth) => 10 * depth + 2 * self.splits,
+            None => 10 * (MAX_SPLIT_DEPTH + 1) + 2 * self.splits,
+        };
+        self.rows + self.propagation_steps + splitting
+    }
+
+    pub fn get_label(&self) -> &'static str {
+        match (self.candidates, self.get_score()) {
+            (0, _) => "contradictory",
+            (1, 0..=9) => "easy",
+            (1, 10..=19) => "medium",
+            (1, 20..=34) => "hard",
+            (1, _) => "expert",
+            _ => "not unique",
+        }
+    }
+}
+
+impl Display for Rating {
+    fn fmt(&self, format: &mut Formatter) -> Result<(), Error> {
+        writeln!(
+            format,
+            "difficulty: {} (score {})",
+            self.get_label(),
+            self.get_score()
+        )?;
+        writeln!(format, "rows: {}", self.rows)?;
+        writeln!(
+            format,
+            "codes consistent with every row: {}",
+            self.candidates
+        )?;
+        writeln!(
+            format,
+            "propagation steps: {} rounds of ruling out colors one row at a time",
+            self.propagation_steps
+        )?;
+        match (self.candidates, self.split_depth) {
+            (1, Some(0)) => writeln!(format, "no case splitting, the rows alone pin down the code"),
+            (1, Some(depth)) => writeln!(
+                format,
+                "case splitting: {} colors ruled out by assuming them and finding a contradiction, nested {} deep",
+                self.splits, depth
+            ),
+            (1, None) => writeln!(
+                format,
+                "case splitting: more than {} nested assumptions, needs trial and error",
+                MAX_SPLIT_DEPTH
+            ),
+            _ => writeln!(format, "case splitting: no single code to deduce"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::Colors;
+    use crate::difficulty::{rate, Rating};
+    use crate::evaluation::Evaluation;
+    use crate::mastermind_state::{MastermindState, Values};
+    use crate::puzzle::generate_puzzle;
+
+    fn row(values: Values, correct_match: u8, color_present: u8) -> MastermindState {
+        MastermindState::new(values, Evaluation::new(correct_match, color_present))
+    }
+
+    #[test]
+    fn rows_that_pin_down_every_position_are_easy() {
+        use Colors::*;
+        // one color is ruled out per row, the last color is left in every position
+        let rows = [
+            row([Red; 4], 0, 0),
+            row([Green; 4], 0, 0),
+            row([Blue; 4], 0, 0),
+            row([Yellow; 4], 0, 0),
+            row([White; 4], 0, 0),
+        ];
+        let rating = rate(&rows);
+        assert_eq!(
+            Rating {
+                rows: 5,
+                candidates: 1,
+                propagation_steps: 1,
+                split_depth: Some(0),
+                splits: 0,
+            },
+            rating
+        );
+        assert_eq!(6, rating.get_score());
+        assert_eq!("easy", rating.get_label());
+        assert!(rating.to_string().contains("no case splitting"));
+    }
+
+    #[test]
+    fn positions_not_unique() {
+        let rating = rate(&[row([Colors::Red; 4], 0, 0)]);
+        assert_eq!(625, rating.candidates);
+        assert_eq!(None, rating.split_depth);
+        assert_eq!("not unique", rating.get_label());
+        let rating = rate(&[row([Colors::Red; 4], 1, 0), row([Colors::Red; 4], 2, 0)]);
+        assert_eq!("contradictory", rating.get_label());
+    }
+
+    #[test]
+    fn derangements_are_found_by_propagation() {
+        use Colors::*;
+        let rows = [
+            row([Red, Green, Blue, Yellow], 0, 4),
+            row([Green, Red, Yellow, Blue], 0, 4),
+            row([Yellow, Blue, Red, Green], 0, 4),
+        ];
+        let rating = rate(&rows);
+        assert_eq!(Some(0), rating.split_depth);
+        assert_eq!(1, rating.propagation_steps);
+    }
+
+    #[test]
+    fn contradictions_need_case_splitting() {
+        use Colors::*;
+        let rows = [
+            row([Green, Blue, Yellow, Black], 1, 1),
+            row([White, Black, Yellow, White], 0, 2),
+            row([Blue, White, White, Red], 0, 1),
+            row([Black, Yellow, Yellow, Black], 2, 0),
+        ];
+        let rating = rate(&rows);
+        assert_eq!(
+            Rating {
+                rows: 4,
+                candidates: 1,
+                propagation_steps: 3,
+                split_depth: Some(1),
+                splits: 1,
+            },
+            rating
+        );
+        assert_eq!(19, rating.get_score());
+        assert_eq!("medium", rating.get_label());
+        assert!(rating
+            .to_string()
+            .contains("case splitting: 1 colors ruled out by assuming them"));
+    }
+
+    #[test]
+    fn generated_puzzles_can_be_rated() {
+        let puzzle = generate_puzzle(11, 4, 3).unwrap();
+        let rating = rate(puzzle.get_rows());
+        assert_eq!(4, rating.rows);
+        assert_eq!(1, rating.candidates);
+        assert!(rating.get_score() >= rating.rows);
+    }
+}
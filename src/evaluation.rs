@@ -1,3 +1,4 @@
+use crate::mastermind_state::NUM_ELEMENTS;
 use crate::util::{BLACK, DOTS, RESET, WHITE};
 use std::fmt::{Display, Error, Formatter};
 
@@ -23,6 +24,17 @@ impl Evaluation {
     }
 }
 
+// black and white pegs separated by a comma, e.g. "1,2"
+pub fn parse_evaluation(word: &str) -> Option<Evaluation> {
+    let (correct_match, color_present) = word.split_once(',')?;
+    let correct_match = correct_match.parse::<u8>().ok()?;
+    let color_present = color_present.parse::<u8>().ok()?;
+    if correct_match as usize + color_present as usize > NUM_ELEMENTS {
+        return None;
+    }
+    Some(Evaluation::new(correct_match, color_present))
+}
+
 impl Display for Evaluation {
     fn fmt(&self, format: &mut Formatter) -> Result<(), Error> {
         // each square seems to consume 3 bytes
@@ -38,7 +50,7 @@ impl Display for Evaluation {
 
 #[cfg(test)]
 mod test {
-    use crate::evaluation::Evaluation;
+    use crate::evaluation::{parse_evaluation, Evaluation};
 
     #[test]
     fn new() {
@@ -49,6 +61,15 @@ mod test {
         assert_eq!(y, eval.get_color_present());
     }
 
+    #[test]
+    fn parse() {
+        assert_eq!(Some(Evaluation::new(1, 2)), parse_evaluation("1,2"));
+        assert_eq!(Some(Evaluation::new(4, 0)), parse_evaluation("4,0"));
+        assert_eq!(None, parse_evaluation("3,2"));
+        assert_eq!(None, parse_evaluation("1 2"));
+        assert_eq!(None, parse_evaluation("a,1"));
+    }
+
     #[test]
     fn display() {
         assert_eq!(
@@ -0,0 +1,184 @@
+use crate::colors::Colors;
+use crate::mastermind_state::{MastermindState, NUM_ELEMENTS};
+use crate::multi_digit_solver::PossibleColors;
+
+// follows a game and says what every new feedback rules out
+pub struct Explainer {
+    possible: PossibleColors,
+    history: Vec<MastermindState>,
+}
+
+impl Explainer {
+    pub fn new() -> Self {
+        Explainer {
+            possible: PossibleColors::new_with_all_colors(),
+            history: Vec::new(),
+        }
+    }
+
+    // e.g. after a guess was taken back
+    pub fn new_with_guesses(guesses: &[MastermindState]) -> Self {
+        let mut explainer = Explainer::new();
+        for state in guesses {
+            explainer.add_guess(*state);
+        }
+        explainer
+    }
+
+    // lines like "position 2 cannot be Red because guess 3 scored zero blacks"
+    pub fn add_guess(&mut self, state: MastermindState) -> Vec<String> {
+        let values = state.get_values();
+        let eval = state.get_evaluation();
+        let number = self.history.len() + 1;
+        let mut deductions = Vec::new();
+
+        let before = self.possible.clone();
+        self.possible.remove_absent_colors(&values, &eval);
+        let reason = format!("guess {} scored no pegs at all", number);
+        deductions.extend(self.describe_removed(&before, &reason));
+
+        let before = self.possible.clone();
+        self.possible.reduce_colors(&values, &eval);
+        let reason = format!("guess {} scored zero blacks", number);
+        deductions.extend(self.describe_removed(&before, &reason));
+
+        for (index, old) in self.history.iter().enumerate() {
+            let before = self.possible.clone();
+            self.possible.reduce_colors_with_swap(
+                &values,
+                &eval,
+                &old.get_values(),
+                &old.get_evaluation(),
+            );
+            let reason = format!(
+                "guess {} swaps two colors of guess {} and the blacks changed from {} to {}",
+                number,
+                index + 1,
+                old.get_evaluation().get_correct_match(),
+                eval.get_correct_match()
+            );
+            deductions.extend(self.describe_removed(&before, &reason));
+        }
+
+        self.history.push(state);
+        deductions
+    }
+
+    fn describe_removed(&self, before: &PossibleColors, reason: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        for position in 0..NUM_ELEMENTS {
+            let now = self.possible.get_colors(position);
+            let removed: Vec<String> = Colors::iter()
+                .filter(|c| before.get_colors(position).contains(c) && !now.contains(c))
+                .map(|c| format!("{:?}", c))
+                .collect();
+            if removed.is_empty() {
+                continue;
+            }
+            lines.push(format!(
+                "position {} cannot be {} because {}",
+                position + 1,
+                removed.join(" or "),
+                reason
+            ));
+            if now.is_empty() {
+                lines.push(format!(
+                    "so no color is left for position {}, the feedback contradicts an earlier guess",
+                    position + 1
+                ));
+            } else if 1 == now.len() {
+                let color = now.iter().next().unwrap();
+                lines.push(format!("so position {} must be {:?}", position + 1, color));
+            }
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::Colors;
+    use crate::evaluation::Evaluation;
+    use crate::explain::Explainer;
+    use crate::mastermind_state::MastermindState;
+
+    fn row(values: [Colors; 4], correct_match: u8, color_present: u8) -> MastermindState {
+        MastermindState::new(values, Evaluation::new(correct_match, color_present))
+    }
+
+    #[test]
+    fn zero_blacks_rule_out_the_positions() {
+        use Colors::*;
+        let mut explainer = Explainer::new();
+        assert_eq!(
+            vec![
+                "position 1 cannot be Red because guess 1 scored zero blacks",
+                "position 2 cannot be Green because guess 1 scored zero blacks",
+                "position 3 cannot be Blue because guess 1 scored zero blacks",
+                "position 4 cannot be Yellow because guess 1 scored zero blacks",
+            ],
+            explainer.add_guess(row([Red, Green, Blue, Yellow], 0, 2))
+        );
+        assert!(explainer
+            .add_guess(row([Red, Green, Blue, Yellow], 1, 2))
+            .is_empty());
+    }
+
+    #[test]
+    fn no_pegs_rule_out_the_colors() {
+        use Colors::*;
+        let mut explainer = Explainer::new();
+        let lines = explainer.add_guess(row([Red, Red, Green, Green], 0, 0));
+        assert_eq!(4, lines.len());
+        assert_eq!(
+            "position 1 cannot be Red or Green because guess 1 scored no pegs at all",
+            lines[0]
+        );
+        explainer.add_guess(row([Blue, Blue, Yellow, Yellow], 0, 0));
+        let lines = explainer.add_guess(row([White, White, White, White], 0, 0));
+        assert_eq!("so position 1 must be Black", lines[1]);
+    }
+
+    #[test]
+    fn swaps_gaining_blacks_place_the_colors() {
+        use Colors::*;
+        let mut explainer = Explainer::new();
+        explainer.add_guess(row([Red, Green, Blue, Yellow], 0, 4));
+        let lines = explainer.add_guess(row([Green, Red, Blue, Yellow], 2, 2));
+        assert!(lines.contains(&String::from(
+            "position 1 cannot be Blue or Yellow or White or Black because guess 2 swaps two colors of guess 1 and the blacks changed from 0 to 2"
+        )));
+        assert!(lines.contains(&String::from("so position 1 must be Green")));
+        assert!(lines.contains(&String::from("so position 2 must be Red")));
+        // swapping without a gain proves nothing, the colors could be in both positions
+        let lines = explainer.add_guess(row([Green, Red, Yellow, Blue], 2, 2));
+        assert!(lines.is_empty());
+    }
+
+    #[test]
+    fn swap_does_not_bring_back_ruled_out_colors() {
+        use Colors::*;
+        let mut explainer = Explainer::new();
+        explainer.add_guess(row([Green, White, White, White], 0, 1));
+        explainer.add_guess(row([Red, Green, Blue, Yellow], 0, 4));
+        // the swap puts Green in position 1, which guess 1 already ruled out
+        let lines = explainer.add_guess(row([Green, Red, Blue, Yellow], 2, 2));
+        assert!(lines.contains(&String::from(
+            "so no color is left for position 1, the feedback contradicts an earlier guess"
+        )));
+        assert!(explainer.possible.get_colors(0).is_empty());
+        assert!(lines.contains(&String::from("so position 2 must be Red")));
+    }
+
+    #[test]
+    fn explainer_is_rebuilt_from_the_guesses() {
+        use Colors::*;
+        let guesses = [
+            row([Red, Red, Green, Green], 0, 0),
+            row([Blue, Blue, Yellow, Yellow], 0, 0),
+        ];
+        let mut explainer = Explainer::new_with_guesses(&guesses);
+        let lines = explainer.add_guess(row([White, White, White, White], 0, 0));
+        assert_eq!("so position 1 must be Black", lines[1]);
+    }
+}
@@ -0,0 +1,214 @@
+use crate::candidate_set::CandidateSet;
+use crate::code::{pack, unpack, Code};
+use crate::colors::Colors;
+use crate::mastermind::Mastermind;
+use crate::mastermind_state::NUM_ELEMENTS;
+use crate::scoring_rule::ScoringRule;
+
+// shades from rare to common, an impossible color is left blank
+const SHADES: [char; 4] = ['░', '▒', '▓', '█'];
+
+// space between the board and the panel
+const GAP: usize = 4;
+
+// how many remaining candidates have each color in each position
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatMap {
+    // indexed by position and then by color
+    counts: Vec<Vec<usize>>,
+    total: usize,
+}
+
+impl HeatMap {
+    // every allowed code which would have given the same feedback to every guess
+    pub fn new<R: ScoringRule>(mm: &Mastermind<R>) -> Self {
+        let codes: Vec<Code> = mm.get_allowed_guesses().iter().map(pack).collect();
+        let mut candidates = CandidateSet::from_codes(&codes);
+        let rule = mm.get_rule();
+        for state in mm.get_guesses() {
+            let values = state.get_values();
+            let guess = pack(&values);
+            let feedback = state.get_evaluation();
+            // a guess with the feedback of the secret stays, the player cannot tell more
+            let solved = rule.score(&values, &values) == feedback;
+            candidates.retain(|candidate| {
+                (solved || candidate != guess) && rule.score_code(candidate, guess) == feedback
+            });
+        }
+        let mut counts = vec![vec![0; Colors::len() as usize]; NUM_ELEMENTS];
+        for candidate in candidates.iter() {
+            for (position, color) in unpack(candidate).iter().enumerate() {
+                counts[position][*color as usize] += 1;
+            }
+        }
+        HeatMap {
+            counts,
+            total: candidates.len(),
+        }
+    }
+
+    pub fn get_count(&self, position: usize, color: Colors) -> usize {
+        self.counts[position][color as usize]
+    }
+
+    pub fn get_total(&self) -> usize {
+        self.total
+    }
+
+    fn get_shade(&self, count: usize) -> char {
+        if 0 == count {
+            return ' ';
+        }
+        // count is at most total, so this is always a valid index
+        SHADES[(count * SHADES.len() - 1) / self.total]
+    }
+
+    // a header with the colors and one line per position
+    pub fn render(&self) -> Vec<String> {
+        let mut header = format!("{:<10}", format!("{} codes", self.get_total()));
+        for color in Colors::iter() {
+            header.push_str(&format!("    {}", color));
+        }
+        let mut lines = vec![header];
+        for position in 0..NUM_ELEMENTS {
+            let mut line = format!("{:<10}", format!("pos {}", position + 1));
+            for color in Colors::iter() {
+                match self.get_count(position, *color) {
+                    0 => line.push_str("     "),
+                    count => line.push_str(&format!("{:>4}{}", count, self.get_shade(count))),
+                }
+            }
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+// columns taken on the terminal, the color escape sequences take none
+fn visible_len(text: &str) -> usize {
+    let mut len = 0;
+    let mut escape = false;
+    for c in text.chars() {
+        match c {
+            '\x1B' => escape = true,
+            'm' if escape => escape = false,
+            _ if escape => {}
+            _ => len += 1,
+        }
+    }
+    len
+}
+
+// the panel is shown to the right of the board
+pub fn show_with_panel(board: &[String], panel: &[String]) -> String {
+    let width = board
+        .iter()
+        .map(|line| visible_len(line))
+        .max()
+        .unwrap_or(0);
+    let mut text = String::new();
+    for row in 0..board.len().max(panel.len()) {
+        let left = board.get(row).map_or("", String::as_str);
+        let right = panel.get(row).map_or("", String::as_str);
+        let padding = width - visible_len(left) + GAP;
+        text.push_str(format!("{}{}{}", left, " ".repeat(padding), right).trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::Colors;
+    use crate::heat_map::{show_with_panel, visible_len, HeatMap};
+    use crate::mastermind::Mastermind;
+    use crate::scoring_rule::{BlackOnly, ColorsPresent};
+
+    #[test]
+    fn counts_come_from_the_candidates() {
+        let mut mm = Mastermind::new_with_state([Colors::Green; 4]);
+        mm.set_verbose(false);
+        let heat_map = HeatMap::new(&mm);
+        assert_eq!(1296, heat_map.get_total());
+        assert_eq!(216, heat_map.get_count(0, Colors::Red));
+
+        // no red anywhere and the blue of the second guess is never in the first position
+        mm.guess([Colors::Red; 4]);
+        mm.guess([Colors::Green, Colors::Blue, Colors::Blue, Colors::Blue]);
+        let heat_map = HeatMap::new(&mm);
+        assert_eq!(0, heat_map.get_count(0, Colors::Red));
+        assert_eq!(0, heat_map.get_count(2, Colors::Red));
+        for position in 0..4 {
+            let sum: usize = Colors::iter()
+                .map(|color| heat_map.get_count(position, *color))
+                .sum();
+            assert_eq!(heat_map.get_total(), sum);
+        }
+        assert_eq!(0, heat_map.get_count(0, Colors::Blue));
+        assert!(0 < heat_map.get_count(1, Colors::Blue));
+    }
+
+    #[test]
+    fn counts_follow_the_scoring_rule() {
+        let mut mm = Mastermind::new_with_state_and_rule([Colors::Green; 4], BlackOnly);
+        mm.set_verbose(false);
+        mm.guess([Colors::Green, Colors::Red, Colors::Red, Colors::Red]);
+        let heat_map = HeatMap::new(&mm);
+        // one black, either the green or one of the reds is right
+        assert_eq!(125 + 3 * 125, heat_map.get_total());
+    }
+
+    #[test]
+    fn solved_code_is_kept() {
+        let secret = [Colors::Green, Colors::Red, Colors::Red, Colors::Black];
+        let mut mm = Mastermind::new_with_state_and_rule(secret, BlackOnly);
+        mm.set_verbose(false);
+        mm.guess([Colors::Red; 4]);
+        mm.guess(secret);
+        let heat_map = HeatMap::new(&mm);
+        assert_eq!(1, heat_map.get_total());
+        assert_eq!(1, heat_map.get_count(0, Colors::Green));
+        assert_eq!(1, heat_map.get_count(3, Colors::Black));
+        assert!(heat_map.render()[0].starts_with("1 codes"));
+    }
+
+    #[test]
+    fn solved_comes_from_the_feedback() {
+        // the colors rule gives a permutation of the secret the feedback of the secret
+        let secret = [Colors::Green, Colors::Red, Colors::Blue, Colors::Black];
+        let guess = [Colors::Red, Colors::Green, Colors::Blue, Colors::Black];
+        let mut mm = Mastermind::new_with_state_and_rule(secret, ColorsPresent);
+        mm.set_verbose(false);
+        mm.guess(guess);
+        let heat_map = HeatMap::new(&mm);
+        // the 24 orders of the four colors are left, the guess among them
+        assert_eq!(24, heat_map.get_total());
+        assert_eq!(6, heat_map.get_count(0, Colors::Red));
+    }
+
+    #[test]
+    fn render_shades_the_counts() {
+        let mut mm = Mastermind::new_with_state([Colors::Green; 4]);
+        mm.set_verbose(false);
+        mm.guess([Colors::Red; 4]);
+        let lines = HeatMap::new(&mm).render();
+        assert_eq!(5, lines.len());
+        assert!(lines[0].starts_with("625 codes"));
+        // red is impossible, so its column is blank
+        assert_eq!(
+            format!("{:<10}{}{}", "pos 1", " ".repeat(5), " 125░".repeat(5)),
+            lines[1]
+        );
+    }
+
+    #[test]
+    fn panel_is_next_to_the_board() {
+        let board = vec![String::from("\x1B[31mab\x1B[0m"), String::from("abcd")];
+        let panel = vec![String::from("x"), String::from("y"), String::from("z")];
+        assert_eq!(2, visible_len(&board[0]));
+        assert_eq!(
+            "\x1B[31mab\x1B[0m      x\nabcd    y\n        z\n",
+            show_with_panel(&board, &panel)
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use crate::colors::Colors;
+use crate::mastermind::Mastermind;
+use crate::mastermind_state::{parse_guess, MastermindState, Values, NUM_ELEMENTS};
+use crate::solver::Solver;
+use std::fmt::{Display, Error, Formatter};
+use std::io::{stdin, stdout, Error as IoError, ErrorKind, Write};
+use std::process::{Command, Stdio};
+
+// asks the named player for a secret code
+pub type SecretFn = fn(&str) -> Result<Values, IoError>;
+
+pub struct Player {
+    name: String,
+    // humans choose their secret codes, computers pick random ones
+    human: bool,
+    solver: Solver,
+}
+
+impl Player {
+    pub fn new(name: &str, human: bool, solver: Solver) -> Self {
+        Player {
+            name: String::from(name),
+            human,
+            solver,
+        }
+    }
+}
+
+// the codemaker scores a point for every guess of the codebreaker
+pub struct Scoreboard {
+    names: [String; 2],
+    points: [usize; 2],
+    // codemaker and number of guesses of every round
+    rounds: Vec<(usize, usize)>,
+}
+
+impl Scoreboard {
+    fn new(players: &[Player; 2]) -> Self {
+        Scoreboard {
+            names: [players[0].name.clone(), players[1].name.clone()],
+            points: [0; 2],
+            rounds: Vec::new(),
+        }
+    }
+
+    fn add_round(&mut self, codemaker: usize, guesses: usize) {
+        self.points[codemaker] += guesses;
+        self.rounds.push((codemaker, guesses));
+    }
+
+    // None for a draw
+    pub fn get_winner(&self) -> Option<&str> {
+        match self.points[0].cmp(&self.points[1]) {
+            std::cmp::Ordering::Greater => Some(&self.names[0]),
+            std::cmp::Ordering::Less => Some(&self.names[1]),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+impl Display for Scoreboard {
+    fn fmt(&self, format: &mut Formatter) -> Result<(), Error> {
+        let width = self.names.iter().map(String::len).max().unwrap().max(9);
+        writeln!(
+            format,
+            "round  {:width$}  guesses",
+            "codemaker",
+            width = width
+        )?;
+        for (i, (codemaker, guesses)) in self.rounds.iter().enumerate() {
+            writeln!(
+                format,
+                "{:5}  {:width$}  {:7}",
+                i + 1,
+                self.names[*codemaker],
+                guesses,
+                width = width
+            )?;
+        }
+        for (name, points) in self.names.iter().zip(self.points.iter()) {
+            writeln!(
+                format,
+                "total  {:width$}  {:7}",
+                name,
+                points,
+                width = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn set_echo(on: bool) {
+    // without a terminal the input is not shown anyway
+    let _ = Command::new("stty")
+        .arg(if on { "echo" } else { "-echo" })
+        .stderr(Stdio::null())
+        .status();
+}
+
+// reads the code without showing it, so the other player cannot see it
+pub fn read_hidden_secret(name: &str) -> Result<Values, IoError> {
+    Colors::show_number_mapping();
+    loop {
+        print!("{}, enter your secret code: ", name);
+        stdout().flush()?;
+        set_echo(false);
+        let mut buf = String::new();
+        let read = stdin().read_line(&mut buf);
+        set_echo(true);
+        println!();
+        if 0 == read? {
+            return Err(IoError::new(
+                ErrorKind::UnexpectedEof,
+                "no secret code entered",
+            ));
+        }
+        match parse_guess(&buf) {
+            Some(values) => return Ok(values),
+            None => println!("a code has {} digits below {}", NUM_ELEMENTS, Colors::len()),
+        }
+    }
+}
+
+// the players take turns as codemaker, player one starts
+pub fn play_hot_seat(
+    players: &[Player; 2],
+    rounds: usize,
+    secret: SecretFn,
+) -> Result<Scoreboard, IoError> {
+    let mut scoreboard = Scoreboard::new(players);
+    for round in 0..rounds {
+        let codemaker = round % 2;
+        let (maker, breaker) = (&players[codemaker], &players[1 - codemaker]);
+        println!(
+            "round {}: {} sets the code, {} guesses",
+            round + 1,
+            maker.name,
+            breaker.name
+        );
+        let values = if maker.human {
+            secret(&maker.name)?
+        } else {
+            MastermindState::new_random_state().get_values()
+        };
+        let mut mm = Mastermind::new_with_state(values);
+        breaker.solver.solve(&mut mm)?;
+        scoreboard.add_round(codemaker, mm.get_guesses().len());
+        print!("{}", scoreboard);
+    }
+    Ok(scoreboard)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::Colors;
+    use crate::hot_seat::{play_hot_seat, Player, Scoreboard};
+    use crate::mastermind_state::Values;
+    use crate::solver::Solver;
+    use crate::{knuth_solver, single_digit_solver};
+    use std::io::{Error, ErrorKind};
+
+    fn black_secret(_: &str) -> Result<Values, Error> {
+        Ok([Colors::Black; 4])
+    }
+
+    fn no_secret(_: &str) -> Result<Values, Error> {
+        Err(Error::from(ErrorKind::UnexpectedEof))
+    }
+
+    #[test]
+    fn codemaker_scores_the_guesses() {
+        let players = [
+            Player::new("alice", true, Solver::Builtin(knuth_solver::solve)),
+            Player::new("bob", false, Solver::Builtin(single_digit_solver::solve)),
+        ];
+        let mut scoreboard = Scoreboard::new(&players);
+        scoreboard.add_round(0, 5);
+        scoreboard.add_round(1, 3);
+        scoreboard.add_round(0, 2);
+        assert_eq!([7, 3], scoreboard.points);
+        assert_eq!(Some("alice"), scoreboard.get_winner());
+        scoreboard.add_round(1, 4);
+        assert_eq!(None, scoreboard.get_winner());
+        assert_eq!(
+            "round  codemaker  guesses\n    1  alice            5\n    2  bob              3\n    3  alice            2\n    4  bob              4\ntotal  alice            7\ntotal  bob              7\n",
+            scoreboard.to_string()
+        );
+    }
+
+    #[test]
+    fn humans_choose_the_secret() {
+        // both break codes with solvers, only the secret entry is human
+        let players = [
+            Player::new("alice", true, Solver::Builtin(knuth_solver::solve)),
+            Player::new("bob", true, Solver::Builtin(knuth_solver::solve)),
+        ];
+        let scoreboard = play_hot_seat(&players, 2, black_secret).unwrap();
+        // the same code is cracked the same way
+        let points = scoreboard.points;
+        assert_eq!(points[0], points[1]);
+        assert!(0 < points[0]);
+    }
+
+    #[test]
+    fn solver_against_solver() {
+        let players = [
+            Player::new("knuth", false, Solver::Builtin(knuth_solver::solve)),
+            Player::new("single", false, Solver::Builtin(single_digit_solver::solve)),
+        ];
+        let scoreboard = play_hot_seat(&players, 4, black_secret).unwrap();
+        assert_eq!(4, scoreboard.rounds.len());
+        assert!(scoreboard.rounds.iter().all(|(_, guesses)| *guesses <= 20));
+        let codemakers: Vec<usize> = scoreboard.rounds.iter().map(|(maker, _)| *maker).collect();
+        assert_eq!(vec![0, 1, 0, 1], codemakers);
+    }
+
+    #[test]
+    fn missing_secret_ends_the_game() {
+        let players = [
+            Player::new("alice", true, Solver::Builtin(knuth_solver::solve)),
+            Player::new("bob", false, Solver::Builtin(knuth_solver::solve)),
+        ];
+        let error = play_hot_seat(&players, 2, no_secret).err().unwrap();
+        assert_eq!(ErrorKind::UnexpectedEof, error.kind());
+    }
+}
@@ -0,0 +1,335 @@
+// JSON API over HTTP/1.1, one request per connection
+//
+//   POST /games                 creates a game                -> {"id":1}
+//   GET  /games/<id>            the guesses so far            -> {"id":1,"solved":false,"guesses":[...]}
+//   POST /games/<id>/guesses    body {"guess":"0123"}         -> {"guess":"0123","correct_match":1,...}
+//   GET  /games/<id>/hint       next guess of knuth_solver    -> {"hint":"0011","candidates":1296}
+//
+// errors are answered with {"error":"<message>"} and a 4xx status
+use crate::candidate_set::CandidateSet;
+use crate::code::pack;
+use crate::knuth_solver;
+use crate::mastermind::{GuessStatus, Mastermind};
+use crate::mastermind_state::{get_string_from_guess, parse_guess, MastermindState, NUM_ELEMENTS};
+use crate::network::SecretFn;
+use crate::scoring_rule::{Classic, ScoringRule};
+use std::collections::HashMap;
+use std::fmt::Write as FmtWrite;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// bigger requests are rejected
+const MAX_BODY: usize = 4096;
+
+// the games of every client, a game is found by its id
+pub struct Games {
+    next_id: u64,
+    games: HashMap<u64, Mastermind>,
+    secret: SecretFn,
+}
+
+impl Games {
+    pub fn new(secret: SecretFn) -> Self {
+        Games {
+            next_id: 1,
+            games: HashMap::new(),
+            secret,
+        }
+    }
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn new(status: u16, body: String) -> Self {
+        Response { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        let message = message.replace('\\', "\\\\").replace('"', "\\\"");
+        Response::new(status, format!("{{\"error\":\"{}\"}}", message))
+    }
+}
+
+fn is_solved(mm: &Mastermind) -> bool {
+    mm.get_guesses()
+        .last()
+        .is_some_and(|state| NUM_ELEMENTS as u8 == state.get_evaluation().get_correct_match())
+}
+
+fn write_state(json: &mut String, state: &MastermindState) {
+    let eval = state.get_evaluation();
+    write!(
+        json,
+        "{{\"guess\":\"{}\",\"correct_match\":{},\"color_present\":{}}}",
+        get_string_from_guess(&state.get_values()),
+        eval.get_correct_match(),
+        eval.get_color_present()
+    )
+    .unwrap();
+}
+
+fn game_json(id: u64, mm: &Mastermind) -> String {
+    let mut json = format!("{{\"id\":{},\"solved\":{},\"guesses\":[", id, is_solved(mm));
+    for (i, state) in mm.get_guesses().iter().enumerate() {
+        if 0 < i {
+            json.push(',');
+        }
+        write_state(&mut json, state);
+    }
+    json.push_str("]}");
+    json
+}
+
+// the string value of a key in a flat JSON object
+fn get_json_string(body: &str, key: &str) -> Option<String> {
+    let key = format!("\"{}\"", key);
+    let rest = &body[body.find(&key)? + key.len()..];
+    let rest = rest.trim_start().strip_prefix(':')?.trim_start();
+    let rest = rest.strip_prefix('"')?;
+    Some(String::from(&rest[..rest.find('"')?]))
+}
+
+fn guess(mm: &mut Mastermind, body: &str) -> Response {
+    if is_solved(mm) {
+        return Response::error(409, "game is over");
+    }
+    let values = match get_json_string(body, "guess").and_then(|text| parse_guess(&text)) {
+        Some(values) => values,
+        None => return Response::error(400, "expected {\"guess\":\"<code>\"}"),
+    };
+    let status = mm.guess(values);
+    if GuessStatus::Rejected == status {
+        return Response::error(400, "code is not allowed");
+    }
+    let mut json = String::new();
+    write_state(&mut json, mm.get_guesses().last().unwrap());
+    json.pop();
+    write!(
+        json,
+        ",\"solved\":{},\"guesses\":{}}}",
+        GuessStatus::Success == status,
+        mm.get_guesses().len()
+    )
+    .unwrap();
+    Response::new(200, json)
+}
+
+// runs without the lock on the games, the search can take a while
+fn hint(history: &[MastermindState]) -> Response {
+    let mut candidates = CandidateSet::new_full();
+    for state in history.iter() {
+        let guess = pack(&state.get_values());
+        let eval = state.get_evaluation();
+        candidates.retain(|candidate| Classic.score_code(candidate, guess) == eval);
+    }
+    match knuth_solver::next_guess(history) {
+        Some(guess) => Response::new(
+            200,
+            format!(
+                "{{\"hint\":\"{}\",\"candidates\":{}}}",
+                get_string_from_guess(&guess),
+                candidates.len()
+            ),
+        ),
+        None => Response::error(409, "no code fits the feedback"),
+    }
+}
+
+pub fn handle_request(games: &Mutex<Games>, method: &str, path: &str, body: &str) -> Response {
+    let parts: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let mut games = games.lock().unwrap();
+    if ["games"] == parts.as_slice() {
+        if "POST" != method {
+            return Response::error(405, "method not allowed");
+        }
+        let id = games.next_id;
+        games.next_id += 1;
+        let mut mm = Mastermind::new_with_state((games.secret)());
+        mm.set_verbose(false);
+        games.games.insert(id, mm);
+        return Response::new(201, format!("{{\"id\":{}}}", id));
+    }
+    if parts.len() < 2 || "games" != parts[0] || 3 < parts.len() {
+        return Response::error(404, "not found");
+    }
+    let id = parts[1].parse::<u64>().ok();
+    let mm = match id.and_then(|id| games.games.get_mut(&id)) {
+        Some(mm) => mm,
+        None => return Response::error(404, "no such game"),
+    };
+    match (method, parts.get(2).copied()) {
+        ("GET", None) => Response::new(200, game_json(id.unwrap(), mm)),
+        ("POST", Some("guesses")) => guess(mm, body),
+        ("GET", Some("hint")) => {
+            if is_solved(mm) {
+                return Response::error(409, "game is over");
+            }
+            let history = mm.get_guesses();
+            drop(games);
+            hint(&history)
+        }
+        (_, None) | (_, Some("guesses")) | (_, Some("hint")) => {
+            Response::error(405, "method not allowed")
+        }
+        _ => Response::error(404, "not found"),
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        _ => "Error",
+    }
+}
+
+fn handle_connection(games: &Mutex<Games>, stream: TcpStream) -> Result<(), Error> {
+    let mut input = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    input.read_line(&mut request_line)?;
+    let mut words = request_line.split_whitespace();
+    let (method, path) = match (words.next(), words.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => return Err(Error::new(ErrorKind::InvalidData, "invalid request line")),
+    };
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if 0 == input.read_line(&mut header)? || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse::<usize>().unwrap_or(0);
+            }
+        }
+    }
+    let response = if MAX_BODY < content_length {
+        Response::error(400, "request too big")
+    } else {
+        let mut body = vec![0; content_length];
+        input.read_exact(&mut body)?;
+        handle_request(games, &method, &path, &String::from_utf8_lossy(&body))
+    };
+    let mut output = stream;
+    write!(
+        output,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        response.body.len(),
+        response.body
+    )?;
+    output.flush()
+}
+
+// every connection is handled by its own thread
+pub fn serve_http(listener: TcpListener, secret: SecretFn) -> Result<(), Error> {
+    let games = Arc::new(Mutex::new(Games::new(secret)));
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let games = Arc::clone(&games);
+        thread::spawn(move || handle_connection(&games, stream));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::Colors;
+    use crate::http_api::{get_json_string, handle_request, Games};
+    use crate::mastermind_state::Values;
+    use std::sync::Mutex;
+
+    fn secret() -> Values {
+        [Colors::Red, Colors::Green, Colors::Blue, Colors::Yellow]
+    }
+
+    #[test]
+    fn get_json_string_finds_the_value() {
+        assert_eq!(
+            Some(String::from("0123")),
+            get_json_string("{ \"guess\" : \"0123\" }", "guess")
+        );
+        assert_eq!(None, get_json_string("{\"guess\":123}", "guess"));
+        assert_eq!(None, get_json_string("{}", "guess"));
+    }
+
+    #[test]
+    fn play_a_game() {
+        let games = Mutex::new(Games::new(secret));
+        let response = handle_request(&games, "POST", "/games", "");
+        assert_eq!(
+            (201, "{\"id\":1}"),
+            (response.status, response.body.as_str())
+        );
+
+        let response = handle_request(&games, "POST", "/games/1/guesses", "{\"guess\":\"1032\"}");
+        assert_eq!(200, response.status);
+        assert_eq!(
+            "{\"guess\":\"1032\",\"correct_match\":0,\"color_present\":4,\"solved\":false,\"guesses\":1}",
+            response.body
+        );
+
+        let response = handle_request(&games, "GET", "/games/1/hint", "");
+        assert_eq!(200, response.status);
+        assert!(response.body.contains("\"candidates\":9"));
+
+        let response = handle_request(&games, "POST", "/games/1/guesses", "{\"guess\":\"0123\"}");
+        assert!(response.body.contains("\"solved\":true"));
+
+        let response = handle_request(&games, "GET", "/games/1", "");
+        assert_eq!(
+            "{\"id\":1,\"solved\":true,\"guesses\":[{\"guess\":\"1032\",\"correct_match\":0,\"color_present\":4},{\"guess\":\"0123\",\"correct_match\":4,\"color_present\":0}]}",
+            response.body
+        );
+        let response = handle_request(&games, "POST", "/games/1/guesses", "{\"guess\":\"0123\"}");
+        assert_eq!(409, response.status);
+    }
+
+    #[test]
+    fn errors() {
+        let games = Mutex::new(Games::new(secret));
+        handle_request(&games, "POST", "/games", "");
+        for (method, path, body, status) in [
+            ("GET", "/", "", 404),
+            ("GET", "/games", "", 405),
+            ("GET", "/games/2", "", 404),
+            ("GET", "/games/x", "", 404),
+            ("DELETE", "/games/1", "", 405),
+            ("POST", "/games/1/guesses", "{\"guess\":\"9999\"}", 400),
+            ("POST", "/games/1/guesses", "", 400),
+            ("GET", "/games/1/other", "", 404),
+        ]
+        .iter()
+        {
+            let response = handle_request(&games, method, path, body);
+            assert_eq!(*status, response.status, "{} {}", method, path);
+            assert!(response.body.starts_with("{\"error\":"));
+        }
+    }
+
+    #[test]
+    fn error_messages_are_valid_json() {
+        let games = Mutex::new(Games::new(secret));
+        handle_request(&games, "POST", "/games", "");
+        let response = handle_request(&games, "POST", "/games/1/guesses", "{}");
+        assert_eq!(
+            (
+                400,
+                "{\"error\":\"expected {\\\"guess\\\":\\\"<code>\\\"}\"}"
+            ),
+            (response.status, response.body.as_str())
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use crate::candidate_set::CandidateSet;
+use crate::code::{get_num_codes, pack, unpack, Code};
+use crate::colors::Colors;
+use crate::mastermind::{GuessStatus, Mastermind};
+use crate::mastermind_state::{MastermindState, Values};
+use crate::parallel::min_by_key;
+use crate::scoring_rule::{Classic, ScoringRule};
+use crate::symmetry::Symmetries;
+
+// size of the biggest group of candidates which share the same feedback for the guess
+fn worst_case<R: ScoringRule>(rule: &R, guess: Code, candidates: &CandidateSet) -> usize {
+    let mut partitions: Vec<(R::Feedback, usize)> = Vec::new();
+    for candidate in candidates.iter() {
+        let eval = rule.score_code(candidate, guess);
+        match partitions.iter_mut().find(|(e, _)| *e == eval) {
+            Some((_, size)) => *size += 1,
+            None => partitions.push((eval, 1)),
+        }
+    }
+    partitions.iter().map(|(_, size)| *size).max().unwrap_or(0)
+}
+
+// minimizes the worst case, prefers guesses which could be the solution
+fn best_guess<R: ScoringRule>(rule: &R, guesses: &[Code], candidates: &CandidateSet) -> Code {
+    if candidates.len() <= 2 {
+        return candidates.iter().next().unwrap();
+    }
+    // every guess is scored against every candidate, so this is spread over all cores
+    let best = min_by_key(guesses, |guess| {
+        (
+            worst_case(rule, *guess, candidates),
+            !candidates.contains(*guess),
+        )
+    });
+    guesses[best.unwrap()]
+}
+
+pub fn solve(mm: &mut Mastermind) -> Values {
+    solve_with_rule(mm)
+}
+
+// Knuth's minimax algorithm, the candidates are limited to the allowed guesses
+pub fn solve_with_rule<R: ScoringRule>(mm: &mut Mastermind<R>) -> Values {
+    let guesses: Vec<Code> = mm.get_allowed_guesses().iter().map(pack).collect();
+    // a dictionary breaks the symmetries
+    let symmetric = mm.get_rule().is_symmetric() && guesses.len() == get_num_codes();
+    let mut history = Vec::new();
+    let mut candidates = CandidateSet::from_codes(&guesses);
+    let mut colors: Values = [Colors::Red, Colors::Red, Colors::Green, Colors::Green];
+    if !mm.is_allowed(&colors) {
+        colors = unpack(best_guess(mm.get_rule(), &guesses, &candidates));
+    }
+    while let GuessStatus::Incorrect(e) = mm.guess(colors) {
+        let rule = mm.get_rule();
+        let guess = pack(&colors);
+        candidates.retain(|candidate| candidate != guess && rule.score_code(candidate, guess) == e);
+        history.push(colors);
+        let representatives = if symmetric {
+            Symmetries::new(&history).representatives(&guesses)
+        } else {
+            guesses.clone()
+        };
+        colors = unpack(best_guess(rule, &representatives, &candidates));
+    }
+    colors
+}
+
+// the guess solve would play after this history
+pub fn next_guess(history: &[MastermindState]) -> Option<Values> {
+    if history.is_empty() {
+        return Some([Colors::Red, Colors::Red, Colors::Green, Colors::Green]);
+    }
+    let guesses: Vec<Code> = (0..get_num_codes() as Code).collect();
+    let mut candidates = CandidateSet::new_full();
+    for state in history {
+        let guess = pack(&state.get_values());
+        let eval = state.get_evaluation();
+        candidates
+            .retain(|candidate| candidate != guess && Classic.score_code(candidate, guess) == eval);
+    }
+    if candidates.is_empty() {
+        return None;
+    }
+    let values: Vec<Values> = history.iter().map(|state| state.get_values()).collect();
+    let representatives = Symmetries::new(&values).representatives(&guesses);
+    Some(unpack(best_guess(&Classic, &representatives, &candidates)))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::candidate_set::CandidateSet;
+    use crate::code::{get_num_codes, pack, Code};
+    use crate::colors::Colors;
+    use crate::knuth_solver::{best_guess, next_guess, solve, solve_with_rule, worst_case};
+    use crate::mastermind::Mastermind;
+    use crate::mastermind_state::Values;
+    use crate::scoring_rule::{Classic, ScoringRule, Wordle};
+    use crate::solver::test_utils::check_solution;
+    use crate::solver::SolverFn;
+    use crate::symmetry::Symmetries;
+
+    #[test]
+    fn solve_has_correct_type() {
+        let _solvefn: SolverFn = solve;
+    }
+
+    #[test]
+    fn solve_solves_the_game() {
+        let mut mm = Mastermind::new();
+        let solution = solve(&mut mm);
+        check_solution(&mm.get_initial().get_values(), &mm, &solution);
+    }
+
+    fn all_codes() -> Vec<Code> {
+        (0..get_num_codes() as Code).collect()
+    }
+
+    #[test]
+    fn worst_case_of_knuth_opening() {
+        let opening = pack(&[Colors::Red, Colors::Red, Colors::Green, Colors::Green]);
+        let candidates = CandidateSet::new_full();
+        assert_eq!(256, worst_case(&Classic, opening, &candidates));
+    }
+
+    #[test]
+    fn best_guess_prefers_candidates() {
+        let candidates = CandidateSet::from_codes(&[
+            pack(&[Colors::Red, Colors::Green, Colors::Blue, Colors::Yellow]),
+            pack(&[Colors::Green, Colors::Red, Colors::Blue, Colors::Yellow]),
+            pack(&[Colors::Blue, Colors::Green, Colors::Red, Colors::Yellow]),
+        ]);
+        let guess = best_guess(&Classic, &all_codes(), &candidates);
+        assert!(candidates.contains(guess));
+    }
+
+    #[test]
+    fn best_guess_is_the_same_as_a_single_threaded_search() {
+        let all_codes = all_codes();
+        let mut candidates = CandidateSet::new_full();
+        candidates.retain(|c| Classic.score_code(c, 7) == Classic.score_code(500, 7));
+        let mut expected = all_codes[0];
+        let mut expected_score = (usize::MAX, true);
+        for guess in all_codes.iter() {
+            let score = (
+                worst_case(&Classic, *guess, &candidates),
+                !candidates.contains(*guess),
+            );
+            if score < expected_score {
+                expected = *guess;
+                expected_score = score;
+            }
+        }
+        assert_eq!(expected, best_guess(&Classic, &all_codes, &candidates));
+    }
+
+    #[test]
+    fn representatives_give_the_same_guess_as_all_guesses() {
+        let all_codes = all_codes();
+        let opening = [Colors::Red, Colors::Red, Colors::Green, Colors::Green];
+        for secret in [7, 500, 1000].iter() {
+            let eval = Classic.score_code(*secret, pack(&opening));
+            let mut candidates = CandidateSet::new_full();
+            candidates.retain(|c| Classic.score_code(c, pack(&opening)) == eval);
+            let representatives = Symmetries::new(&[opening]).representatives(&all_codes);
+            assert!(representatives.len() < all_codes.len());
+            assert_eq!(
+                best_guess(&Classic, &all_codes, &candidates),
+                best_guess(&Classic, &representatives, &candidates)
+            );
+        }
+    }
+
+    #[test]
+    fn solve_with_dictionary() {
+        let dictionary: Vec<Values> = vec![
+            [Colors::Black, Colors::Red, Colors::Blue, Colors::White],
+            [Colors::Green, Colors::White, Colors::Red, Colors::Yellow],
+            [Colors::Blue, Colors::White, Colors::Red, Colors::Yellow],
+            [Colors::Yellow, Colors::Yellow, Colors::Red, Colors::Green],
+            [Colors::Yellow, Colors::Blue, Colors::Red, Colors::Green],
+        ];
+        for _ in 0..10 {
+            let mut mm = Mastermind::new_with_dictionary(dictionary.clone());
+            let solution = solve(&mut mm);
+            check_solution(&mm.get_initial().get_values(), &mm, &solution);
+            assert!(mm.get_guesses().len() <= 3);
+        }
+    }
+
+    #[test]
+    fn solve_with_wordle_rule() {
+        let values = [Colors::White, Colors::Blue, Colors::Yellow, Colors::Black];
+        let mut mm = Mastermind::new_with_state_and_rule(values, Wordle);
+        let solution = solve_with_rule(&mut mm);
+        assert!(mm.get_initial().are_values_equal(&solution));
+    }
+
+    #[test]
+    fn next_guess_continues_the_game() {
+        let values = [Colors::Yellow, Colors::Red, Colors::Black, Colors::Red];
+        let mut mm = Mastermind::new_with_state(values);
+        solve(&mut mm);
+        let guesses = mm.get_guesses();
+        for i in 0..guesses.len() {
+            assert_eq!(Some(guesses[i].get_values()), next_guess(&guesses[..i]));
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::code::{get_num_codes, unpack};
+use crate::colors::Colors;
+use crate::mastermind::{GuessStatus, Mastermind};
+use crate::mastermind_state::Values;
+use crate::scoring_rule::ScoringRule;
+use crate::state_of_the_art_solver::AllStates;
+
+// the candidates of the state of the art solver, a code which does not fit the feedback
+// stays a candidate as long as it needs no more lies than the codemaker may tell
+struct LieStates {
+    candidates: AllStates,
+    // lies it takes for every code to be the solution
+    lies: Vec<u8>,
+    max_lies: u8,
+}
+
+impl LieStates {
+    fn new(states: Vec<Values>, max_lies: u8) -> LieStates {
+        LieStates {
+            candidates: AllStates::new(states),
+            lies: vec![0; get_num_codes()],
+            max_lies,
+        }
+    }
+
+    fn reduce<R: ScoringRule>(&mut self, rule: &R, values: &Values, eval: &R::Feedback) -> Values {
+        // success cannot be lied about, so the guess is out for sure
+        let rejected = self.candidates.filter(rule, values, eval);
+        for code in rejected.iter() {
+            self.lies[code as usize] += 1;
+            if self.lies[code as usize] <= self.max_lies {
+                self.candidates.insert(code);
+            }
+        }
+        self.new_pick()
+    }
+
+    // trusts the feedback as much as possible
+    fn new_pick(&self) -> Values {
+        let lies = |code| self.lies[code as usize];
+        let fewest = self.candidates.iter().map(lies).min().unwrap();
+        let trusted = self.candidates.iter().filter(|code| fewest == lies(*code));
+        AllStates::new(trusted.map(unpack).collect())
+            .new_pick()
+            .unwrap()
+    }
+}
+
+pub fn solve(mm: &mut Mastermind) -> Values {
+    solve_with_rule(mm)
+}
+
+pub fn solve_with_rule<R: ScoringRule>(mm: &mut Mastermind<R>) -> Values {
+    let mut states = LieStates::new(mm.get_allowed_guesses(), mm.get_max_lies());
+    let mut colors: Values = [Colors::Red, Colors::Red, Colors::Green, Colors::Green];
+    if !mm.is_allowed(&colors) {
+        colors = states.new_pick();
+    }
+    while let GuessStatus::Incorrect(e) = mm.guess(colors) {
+        colors = states.reduce(mm.get_rule(), &colors, &e);
+    }
+    colors
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lie_solver::solve;
+    use crate::lies::{Liar, LieMode};
+    use crate::mastermind::Mastermind;
+    use crate::scoring_rule::Classic;
+    use crate::solver::test_utils::check_solution;
+    use crate::solver::SolverFn;
+
+    #[test]
+    fn solve_has_correct_type() {
+        let _solvefn: SolverFn = solve;
+    }
+
+    #[test]
+    fn solve_solves_the_game_without_lies() {
+        let mut mm = Mastermind::new();
+        let solution = solve(&mut mm);
+        check_solution(&mm.get_initial().get_values(), &mm, &solution);
+    }
+
+    fn solve_with_liar(mode: LieMode) {
+        for max_lies in 0..3 {
+            let mut mm = Mastermind::new_with_liar(Classic, Liar::new(mode, max_lies));
+            let solution = solve(&mut mm);
+            check_solution(&mm.get_initial().get_values(), &mm, &solution);
+            assert!(mm.get_lies().len() <= max_lies as usize);
+        }
+    }
+
+    #[test]
+    fn solve_with_random_liar() {
+        solve_with_liar(LieMode::Random);
+    }
+
+    #[test]
+    fn solve_with_adversarial_liar() {
+        solve_with_liar(LieMode::Adversarial);
+    }
+}
@@ -0,0 +1,199 @@
+use crate::mastermind_state::{MastermindState, Values};
+use crate::scoring_rule::ScoringRule;
+use crate::util::{get_random_number_u8, get_random_number_usize};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LieMode {
+    // lies with a chance of one half while lies are left
+    Random,
+    // picks the feedback which keeps the most codes consistent with the game so far
+    Adversarial,
+}
+
+// a codemaker who may give wrong feedback up to max_lies times per game
+#[derive(Debug, Clone, PartialEq)]
+pub struct Liar {
+    mode: LieMode,
+    max_lies: u8,
+    // indices of the guesses which were answered with a lie
+    lies: Vec<usize>,
+}
+
+impl Liar {
+    pub fn new(mode: LieMode, max_lies: u8) -> Self {
+        Liar {
+            mode,
+            max_lies,
+            lies: Vec::new(),
+        }
+    }
+
+    pub fn get_max_lies(&self) -> u8 {
+        self.max_lies
+    }
+
+    pub fn get_lies(&self) -> &[usize] {
+        &self.lies
+    }
+
+    // feedback for the next guess, codes are all possible secrets
+    pub fn answer<R: ScoringRule>(
+        &mut self,
+        rule: &R,
+        codes: &[Values],
+        history: &[MastermindState<R::Feedback>],
+        guess: &Values,
+        truth: R::Feedback,
+    ) -> R::Feedback {
+        if self.lies.len() >= self.max_lies as usize {
+            return truth;
+        }
+        let answer = match self.mode {
+            LieMode::Random => Liar::random_answer(rule, codes, guess, truth),
+            LieMode::Adversarial => self.adversarial_answer(rule, codes, history, guess, truth),
+        };
+        if answer != truth {
+            self.lies.push(history.len());
+        }
+        answer
+    }
+
+    // feedback the guess would get if another code was the secret
+    fn random_answer<R: ScoringRule>(
+        rule: &R,
+        codes: &[Values],
+        guess: &Values,
+        truth: R::Feedback,
+    ) -> R::Feedback {
+        if 0 == get_random_number_u8(2) {
+            return truth;
+        }
+        let lies: Vec<R::Feedback> = codes
+            .iter()
+            .filter(|code| *code != guess)
+            .map(|code| rule.score(code, guess))
+            .filter(|eval| *eval != truth)
+            .collect();
+        if lies.is_empty() {
+            truth
+        } else {
+            lies[get_random_number_usize(lies.len())]
+        }
+    }
+
+    fn adversarial_answer<R: ScoringRule>(
+        &self,
+        rule: &R,
+        codes: &[Values],
+        history: &[MastermindState<R::Feedback>],
+        guess: &Values,
+        truth: R::Feedback,
+    ) -> R::Feedback {
+        // the codebreaker keeps every code which needs at most max_lies lies
+        let mut codes_with_lies: Vec<(R::Feedback, usize)> = Vec::new();
+        for code in codes.iter().filter(|code| *code != guess) {
+            let lies = history
+                .iter()
+                .filter(|state| rule.score(code, &state.get_values()) != state.get_evaluation())
+                .count();
+            if lies <= self.max_lies as usize {
+                codes_with_lies.push((rule.score(code, guess), lies));
+            }
+        }
+
+        let mut answers: Vec<R::Feedback> = Vec::new();
+        for (eval, _) in codes_with_lies.iter() {
+            if !answers.contains(eval) {
+                answers.push(*eval);
+            }
+        }
+
+        let mut best = truth;
+        let mut best_count = 0;
+        for answer in answers {
+            let count = codes_with_lies
+                .iter()
+                .filter(|(eval, lies)| {
+                    lies + usize::from(*eval != answer) <= self.max_lies as usize
+                })
+                .count();
+            if count > best_count || (count == best_count && answer == truth) {
+                best = answer;
+                best_count = count;
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::Colors;
+    use crate::evaluation::Evaluation;
+    use crate::lies::{Liar, LieMode};
+    use crate::mastermind_state::{get_all_values, MastermindState};
+    use crate::scoring_rule::{Classic, ScoringRule};
+
+    #[test]
+    fn liar_without_lies_tells_the_truth() {
+        let mut liar = Liar::new(LieMode::Adversarial, 0);
+        let guess = [Colors::Red; 4];
+        let truth = Evaluation::new(1, 0);
+        let answer = liar.answer(&Classic, &get_all_values(), &[], &guess, truth);
+        assert_eq!(truth, answer);
+        assert!(liar.get_lies().is_empty());
+    }
+
+    #[test]
+    fn random_liar_records_lies() {
+        let mut liar = Liar::new(LieMode::Random, 1);
+        let guess = [Colors::Red; 4];
+        let truth = Evaluation::new(1, 0);
+        let codes = get_all_values();
+        let mut answer = truth;
+        while liar.get_lies().is_empty() {
+            answer = liar.answer(&Classic, &codes, &[], &guess, truth);
+        }
+        assert!(answer != truth);
+        assert_eq!(vec![0], liar.get_lies().to_vec());
+        assert_eq!(truth, liar.answer(&Classic, &codes, &[], &guess, truth));
+    }
+
+    fn codes_within_lies(history: &[MastermindState], max_lies: usize) -> usize {
+        get_all_values()
+            .iter()
+            .filter(|code| {
+                history
+                    .iter()
+                    .filter(|state| {
+                        Classic.score(code, &state.get_values()) != state.get_evaluation()
+                    })
+                    .count()
+                    <= max_lies
+            })
+            .count()
+    }
+
+    #[test]
+    fn adversarial_liar_keeps_most_codes_possible() {
+        let mut liar = Liar::new(LieMode::Adversarial, 1);
+        let secret = [Colors::Red, Colors::Green, Colors::Blue, Colors::Yellow];
+        let codes = get_all_values();
+
+        // every code fits with at most one lie, so the truth is as good as any lie
+        let guess = [Colors::Red, Colors::Red, Colors::Green, Colors::Green];
+        let truth = Classic.score(&secret, &guess);
+        assert_eq!(truth, liar.answer(&Classic, &codes, &[], &guess, truth));
+        assert!(liar.get_lies().is_empty());
+
+        let mut history = vec![MastermindState::new(guess, truth)];
+        let guess = [Colors::Blue, Colors::Blue, Colors::Yellow, Colors::Yellow];
+        let truth = Classic.score(&secret, &guess);
+        let answer = liar.answer(&Classic, &codes, &history, &guess, truth);
+        let mut honest_history = history.clone();
+        honest_history.push(MastermindState::new(guess, truth));
+        history.push(MastermindState::new(guess, answer));
+        assert!(codes_within_lies(&history, 1) > codes_within_lies(&honest_history, 1));
+        assert_eq!(vec![1], liar.get_lies().to_vec());
+    }
+}
@@ -1,25 +1,823 @@
+mod bot;
+mod candidate_set;
+mod code;
 mod colors;
+mod daily;
+mod dictionary;
+mod difficulty;
 mod evaluation;
+mod explain;
+mod heat_map;
+mod hot_seat;
+mod http_api;
+mod knuth_solver;
+mod lie_solver;
+mod lies;
 mod manual_solver;
 mod mastermind;
 mod mastermind_state;
 mod multi_digit_solver;
+mod network;
+mod opening;
+mod opening_book;
+mod parallel;
+mod position_evaluation;
+mod puzzle;
+mod score_table;
+mod scoring_rule;
 mod single_digit_solver;
 mod solver;
 mod state_of_the_art_solver;
+mod static_solver;
+mod stats;
+mod strategy_tree;
+mod symmetry;
+mod time_control;
+mod tournament;
 mod util;
+mod wordle_evaluation;
 
+use code::unpack;
+use colors::Colors;
+use dictionary::load_dictionary;
+use evaluation::parse_evaluation;
+use hot_seat::{play_hot_seat, read_hidden_secret, Player};
+use http_api::serve_http;
+use lies::{Liar, LieMode};
+use manual_solver::{GameResult, PlayOptions};
 use mastermind::Mastermind;
-use solver::parse_args;
+use mastermind_state::{get_string_from_guess, parse_guess, MastermindState};
+use network::{connect, new_random_secret, serve};
+use opening::{get_pattern_string, parse_opening, search_openings};
+use opening_book::{load_book, solve_with_book, OpeningBook};
+use puzzle::{generate_puzzle, get_candidates, get_reasoning_depth, show_solution};
+use scoring_rule::{BlackOnly, Classic, ColorsPresent, Positional, ScoringRule, Wordle};
+use solver::{
+    get_deterministic_solver, get_solver, get_strategy, parse_args, parse_rule_args, Solver,
+    SolverFn,
+};
+use stats::{
+    append_record, get_player_name, get_stats_path, has_completed, load_records, replace_record,
+    show_leaderboard, show_player_stats, GameRecord,
+};
 use std::env;
+use std::net::TcpListener;
+use std::process::exit;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use strategy_tree::StrategyTree;
+use time_control::{SystemClock, TimedInput};
+use tournament::{load_results, Tournament};
 
-fn main() {
-    let mut mm = Mastermind::new();
+// the board in the statistics, e.g. 4x6-classic
+fn get_board_name(rule: &str) -> String {
+    format!(
+        "{}x{}-{}",
+        mastermind_state::NUM_ELEMENTS,
+        Colors::len(),
+        rule
+    )
+}
+
+fn save_record(record: &GameRecord) {
+    if let Err(error) = append_record(&get_stats_path(), record) {
+        eprintln!("could not save the statistics: {}", error);
+    }
+}
+
+// solved games of humans end up in the statistics
+fn record_game(board: &str, guesses: usize, duration: Duration) {
+    let board = get_board_name(board);
+    save_record(&GameRecord::new(
+        &get_player_name(),
+        &board,
+        guesses,
+        duration,
+    ));
+}
 
-    let solver = parse_args(env::args().collect());
+// a game lost on time or a wrong answer to a puzzle still counts
+fn record_lost_game(board: &str, guesses: usize, duration: Duration) {
+    let board = get_board_name(board);
+    save_record(&GameRecord::new_lost(
+        &get_player_name(),
+        &board,
+        guesses,
+        duration,
+    ));
+}
+
+fn play<R: ScoringRule>(
+    mut mm: Mastermind<R>,
+    solver: SolverFn<R>,
+    board: &str,
+    interactive: bool,
+) {
+    let start = Instant::now();
     let solution = solver(&mut mm);
 
+    if mm.get_initial().are_values_equal(&solution) {
+        println!("Game solved in {} steps", mm.get_guesses().len());
+        if interactive {
+            record_game(board, mm.get_guesses().len(), start.elapsed());
+        }
+    }
+}
+
+// a human plays with the options, they do not apply to solvers
+fn play_with_options<R: ScoringRule>(
+    mm: Mastermind<R>,
+    (solver, interactive): (SolverFn<R>, bool),
+    board: &str,
+    options: &PlayOptions,
+) {
+    if interactive {
+        return play_recorded(mm, board, options);
+    }
+    if options.time_control.is_limited() || options.heat_map || options.explain {
+        eprintln!("time limits, the heat map and explanations are only for human players");
+        exit(1);
+    }
+    play(mm, solver, board, false);
+}
+
+fn play_with_rule<R: ScoringRule>(
+    mm: Mastermind<R>,
+    args: &[String],
+    board: &str,
+    options: &PlayOptions,
+) {
+    if options.explain {
+        eprintln!("explanations need black and white pegs, so only the classic rule has them");
+        exit(1);
+    }
+    play_with_options(mm, parse_rule_args(args), board, options);
+}
+
+// the result of a human's game, lost if the time ran out
+fn get_game_record(board: &str, guesses: usize, result: &GameResult) -> GameRecord {
+    let board = get_board_name(board);
+    let mut record = match result.solution {
+        Some(_) => GameRecord::new(&get_player_name(), &board, guesses, result.duration),
+        None => GameRecord::new_lost(&get_player_name(), &board, guesses, result.duration),
+    };
+    record.takebacks = result.takebacks;
+    record
+}
+
+// a human plays on the terminal, the game ends when the input does
+fn play_human<R: ScoringRule>(mm: &mut Mastermind<R>, options: &PlayOptions) -> GameResult {
+    if options.undo {
+        println!("enter undo to take back the last guess");
+    }
+    let input = TimedInput::new();
+    let result = manual_solver::play_with_clock(mm, options, &SystemClock::new(), |wait| {
+        input.get_line(wait)
+    });
+    let result = result.unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    });
+    match result.solution {
+        Some(_) => println!("Game solved in {} steps", mm.get_guesses().len()),
+        None => println!(
+            "Time is up, the code was {}",
+            get_string_from_guess(&mm.get_initial().get_values())
+        ),
+    }
+    if 0 < result.takebacks {
+        println!(
+            "{} guesses taken back, the game is not ranked",
+            result.takebacks
+        );
+    }
+    result
+}
+
+fn play_recorded<R: ScoringRule>(mut mm: Mastermind<R>, board: &str, options: &PlayOptions) {
+    let result = play_human(&mut mm, options);
+    save_record(&get_game_record(board, mm.get_guesses().len(), &result));
+}
+
+// the time limits, the heat map and the explanations of a human player, the other arguments are left
+fn parse_play_options(args: &[String]) -> (PlayOptions, Vec<String>) {
+    manual_solver::parse_play_options(args).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    })
+}
+
+// mastermind [solver] [black|colors|positional|wordle] [game=SECONDS] [guess=SECONDS] [heat-map] [explain]
+// mastermind practice [black|colors|positional|wordle] [...], every unknown solver is a human
+fn play_game(args: &[String], undo: bool) {
+    let (mut options, args) = parse_play_options(args);
+    options.undo = undo;
+    // the optional second argument selects the scoring rule
+    let rule = match args.get(2).map(String::as_str) {
+        Some(rule @ ("black" | "colors" | "positional" | "wordle")) => String::from(rule),
+        _ => String::from("classic"),
+    };
+    match rule.as_str() {
+        "black" => play_with_rule(Mastermind::new_with_rule(BlackOnly), &args, &rule, &options),
+        "colors" => play_with_rule(
+            Mastermind::new_with_rule(ColorsPresent),
+            &args,
+            &rule,
+            &options,
+        ),
+        "positional" => play_with_rule(
+            Mastermind::new_with_rule(Positional),
+            &args,
+            &rule,
+            &options,
+        ),
+        "wordle" => play_with_rule(Mastermind::new_with_rule(Wordle), &args, &rule, &options),
+        // explained games are kept apart in the statistics
+        _ if options.explain => {
+            play_with_options(Mastermind::new(), parse_args(args), "explain", &options)
+        }
+        _ => play_with_options(Mastermind::new(), parse_args(args), &rule, &options),
+    }
+}
+
+// mastermind words <file> [solver]
+fn play_words(args: &[String]) {
+    if 3 > args.len() {
+        eprintln!("usage: {} words <file> [solver]", args[0]);
+        eprintln!(
+            "the file lists the allowed codes, one per line, as letters a to f for the colors"
+        );
+        eprintln!("the board stays 4x6, so every word needs exactly 4 letters from a to f,");
+        eprintln!("other word lists, e.g. of 5-letter words, are rejected");
+        exit(1);
+    }
+    let dictionary = load_dictionary(&args[2]).unwrap_or_else(|error| {
+        eprintln!("could not load {}: {}", args[2], error);
+        exit(1);
+    });
+    // the solver name is the argument after the file
+    let solver_args: Vec<String> = args[..1].iter().chain(args[3..].iter()).cloned().collect();
+    let (solver, interactive) = parse_rule_args(&solver_args);
+    play(
+        Mastermind::new_with_dictionary(dictionary),
+        solver,
+        "words",
+        interactive,
+    );
+}
+
+// mastermind lies <max lies> [random|adversarial] [manual]
+fn play_lies(args: &[String]) {
+    let max_lies = args.get(2).and_then(|arg| arg.parse::<u8>().ok());
+    let max_lies = max_lies.unwrap_or_else(|| {
+        eprintln!(
+            "usage: {} lies <max lies> [random|adversarial] [manual]",
+            args[0]
+        );
+        exit(1);
+    });
+    let mode = match args.get(3).map(String::as_str) {
+        Some("adversarial") => LieMode::Adversarial,
+        _ => LieMode::Random,
+    };
+    let solver: SolverFn = match args.get(4).map(String::as_str) {
+        Some("manual") => manual_solver::solve,
+        _ => lie_solver::solve,
+    };
+    let mut mm = Mastermind::new_with_liar(Classic, Liar::new(mode, max_lies));
+    let start = Instant::now();
+    let solution = solver(&mut mm);
+    if mm.get_initial().are_values_equal(&solution) {
+        println!("Game solved in {} steps", mm.get_guesses().len());
+        if Some("manual") == args.get(4).map(String::as_str) {
+            let board = format!("lies{}", max_lies);
+            record_game(&board, mm.get_guesses().len(), start.elapsed());
+        }
+    }
+    for lie in mm.get_lies() {
+        println!("Feedback for guess {} was a lie", lie + 1);
+    }
+}
+
+fn show_static_guesses() {
+    let guesses = static_solver::get_static_guesses();
+    // the search is greedy, a smaller set may exist
+    println!(
+        "{} guesses distinguish every code (greedy, upper bound):",
+        guesses.len()
+    );
+    for guess in guesses {
+        for c in guess {
+            print!("{}", c);
+        }
+        println!();
+    }
+}
+
+// mastermind tree <solver> [dot|json]
+fn export_tree(args: &[String]) {
+    let usage = || {
+        eprintln!(
+            "usage: {} tree <single|multi|state|knuth|static|bot:<command>> [dot|json]",
+            args[0]
+        );
+        exit(1);
+    };
+    // only deterministic solvers can be expanded into a tree
+    let solver = match args.get(2).and_then(|name| get_solver(name)) {
+        Some(solver) => solver,
+        None => return usage(),
+    };
+    let tree = StrategyTree::build(&solver).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    });
+    match args.get(3).map(String::as_str) {
+        None | Some("dot") => print!("{}", tree.to_dot()),
+        Some("json") => println!("{}", tree.to_json()),
+        _ => return usage(),
+    }
+    let depths = tree.get_depths();
+    eprintln!(
+        "{} secrets, worst case {} guesses, average {:.3} guesses",
+        depths.len(),
+        depths.iter().max().unwrap(),
+        depths.iter().sum::<usize>() as f64 / depths.len() as f64
+    );
+}
+
+// mastermind make-book <solver> [depth]
+fn make_book(args: &[String]) {
+    let solver = args.get(2).and_then(|name| get_deterministic_solver(name));
+    let depth = match args.get(3) {
+        Some(depth) => depth.parse::<usize>().ok(),
+        None => Some(3),
+    };
+    let (solver, depth) = match (solver, depth) {
+        (Some(solver), Some(depth)) => (solver, depth),
+        _ => {
+            eprintln!(
+                "usage: {} make-book <single|multi|state|knuth|static> [depth]",
+                args[0]
+            );
+            exit(1);
+        }
+    };
+    let tree = StrategyTree::build(&Solver::Builtin(solver)).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    });
+    let book = OpeningBook::from_tree(&tree, depth);
+    print!("{}", book);
+    eprintln!("{} positions", book.len());
+}
+
+// mastermind book <file> [state|knuth]
+fn play_book(args: &[String]) {
+    let strategy = get_strategy(args.get(3).map_or("knuth", String::as_str));
+    let strategy = match (args.get(2), strategy) {
+        (Some(_), Some(strategy)) => strategy,
+        _ => {
+            eprintln!("usage: {} book <file> [state|knuth]", args[0]);
+            exit(1);
+        }
+    };
+    let book = load_book(&args[2]).unwrap_or_else(|error| {
+        eprintln!("could not load {}: {}", args[2], error);
+        exit(1);
+    });
+    let mut mm = Mastermind::new();
+    let solution = solve_with_book(&mut mm, &book, strategy);
+    if mm.get_initial().are_values_equal(&solution) {
+        println!("Game solved in {} steps", mm.get_guesses().len());
+    }
+}
+
+// mastermind opening <code|pattern>
+fn play_opening(args: &[String]) {
+    let opening = args.get(2).and_then(|text| parse_opening(text));
+    let opening = opening.unwrap_or_else(|| {
+        eprintln!(
+            "usage: {} opening <code like 0011|pattern like AABB>",
+            args[0]
+        );
+        exit(1);
+    });
+    let mut mm = Mastermind::new();
+    let solution = state_of_the_art_solver::solve_with_opening(&mut mm, opening);
     if mm.get_initial().are_values_equal(&solution) {
         println!("Game solved in {} steps", mm.get_guesses().len());
     }
 }
+
+fn show_opening_search() {
+    let (results, best_worst_case, best_average) = search_openings();
+    println!("pattern  worst case  average");
+    for result in results.iter() {
+        println!(
+            "{}     {:10}  {:.3}",
+            get_pattern_string(&result.opening),
+            result.worst_case,
+            result.average
+        );
+    }
+    println!(
+        "best worst case: {} ({} guesses)",
+        get_pattern_string(&best_worst_case.opening),
+        best_worst_case.worst_case
+    );
+    println!(
+        "best average:    {} ({:.3} guesses)",
+        get_pattern_string(&best_average.opening),
+        best_average.average
+    );
+}
+
+// human, the name of a deterministic solver or a bot
+fn parse_player(name: &str, arg: Option<&String>) -> Option<Player> {
+    match arg.map(String::as_str) {
+        None | Some("human") => Some(Player::new(
+            name,
+            true,
+            Solver::Builtin(manual_solver::solve),
+        )),
+        Some(solver) => Some(Player::new(name, false, get_solver(solver)?)),
+    }
+}
+
+// mastermind hot-seat [rounds] [player 1] [player 2]
+fn play_two_players(args: &[String]) {
+    let rounds = match args.get(2) {
+        Some(rounds) => rounds.parse::<usize>().ok(),
+        None => Some(2),
+    };
+    let player1 = parse_player("player 1", args.get(3));
+    let player2 = parse_player("player 2", args.get(4));
+    let (rounds, players) = match (rounds, player1, player2) {
+        (Some(rounds), Some(player1), Some(player2)) => (rounds, [player1, player2]),
+        _ => {
+            eprintln!(
+                "usage: {} hot-seat [rounds] [human|single|multi|state|knuth|static|bot:<command>] [human|...]",
+                args[0]
+            );
+            exit(1);
+        }
+    };
+    let scoreboard = play_hot_seat(&players, rounds, read_hidden_secret).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    });
+    match scoreboard.get_winner() {
+        Some(name) => println!("{} wins", name),
+        None => println!("draw"),
+    }
+}
+
+// mastermind serve [address]
+fn run_server(args: &[String]) {
+    let address = args.get(2).map_or("0.0.0.0:4040", String::as_str);
+    let listener = TcpListener::bind(address).unwrap_or_else(|error| {
+        eprintln!("could not listen on {}: {}", address, error);
+        exit(1);
+    });
+    println!("listening on {}", listener.local_addr().unwrap());
+    if let Err(error) = serve(listener, new_random_secret) {
+        eprintln!("{}", error);
+        exit(1);
+    }
+}
+
+// mastermind connect <address>
+fn run_client(args: &[String]) {
+    let address = args.get(2).unwrap_or_else(|| {
+        eprintln!("usage: {} connect <host:port>", args[0]);
+        exit(1);
+    });
+    Colors::show_number_mapping();
+    match connect(address, manual_solver::get_guess) {
+        Ok(steps) => println!("Game solved in {} steps", steps),
+        Err(error) => {
+            eprintln!("{}", error);
+            exit(1);
+        }
+    }
+}
+
+// mastermind http [address], only reachable from this machine by default
+fn run_http_server(args: &[String]) {
+    let address = args.get(2).map_or("127.0.0.1:8080", String::as_str);
+    let listener = TcpListener::bind(address).unwrap_or_else(|error| {
+        eprintln!("could not listen on {}: {}", address, error);
+        exit(1);
+    });
+    println!("listening on {}", listener.local_addr().unwrap());
+    if let Err(error) = serve_http(listener, new_random_secret) {
+        eprintln!("{}", error);
+        exit(1);
+    }
+}
+
+// mastermind bot <command> [args...] lets an external solver play one game
+fn play_with_bot(args: &[String]) {
+    let mut mm = Mastermind::new();
+    match Solver::Bot(args[2..].to_vec()).solve(&mut mm) {
+        Ok(solution) if mm.get_initial().are_values_equal(&solution) => {
+            println!("Game solved in {} steps", mm.get_guesses().len())
+        }
+        Ok(_) => {}
+        Err(error) => {
+            eprintln!("bot failed: {}", error);
+            exit(1);
+        }
+    }
+}
+
+// speaks the bot protocol on stdin and stdout, e.g. for mastermind bot mastermind reference-bot
+fn run_reference_bot() {
+    let result = bot::run_bot(
+        std::io::stdin().lock(),
+        std::io::stdout(),
+        state_of_the_art_solver::next_guess,
+    );
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        exit(1);
+    }
+}
+
+// mastermind tournament [seed=N] [secrets=N] [limit=N] [output=FILE] [solver...]
+fn run_tournament(args: &[String]) {
+    let usage = || -> ! {
+        eprintln!(
+            "usage: {} tournament [seed=N] [secrets=N] [limit=N] [output=FILE] [single|multi|state|knuth|static|bot:<command>...]",
+            args[0]
+        );
+        exit(1);
+    };
+    let (mut seed, mut count, mut limit, mut output) = (1, 100, 10, None);
+    let mut solvers = Vec::new();
+    for arg in args[2..].iter() {
+        let number = || {
+            arg.split_once('=')
+                .and_then(|(_, n)| n.parse::<usize>().ok())
+        };
+        match arg.split_once('=').map(|(key, _)| key) {
+            Some("seed") => seed = number().unwrap_or_else(|| usage()) as u64,
+            Some("secrets") => count = number().filter(|n| 0 < *n).unwrap_or_else(|| usage()),
+            Some("limit") => limit = number().unwrap_or_else(|| usage()),
+            Some("output") => output = arg.split_once('=').map(|(_, path)| path),
+            Some(_) => usage(),
+            None => match get_solver(arg) {
+                Some(solver) => solvers.push((arg.clone(), solver)),
+                None => usage(),
+            },
+        }
+    }
+    if solvers.is_empty() {
+        for name in ["single", "multi", "state", "knuth", "static"].iter() {
+            solvers.push((name.to_string(), get_solver(name).unwrap()));
+        }
+    }
+    let tournament = Tournament::run(seed, count, limit, &solvers).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    });
+    print!("{}", tournament.show_leaderboard());
+    if let Some(path) = output {
+        if let Err(error) = std::fs::write(path, tournament.to_string()) {
+            eprintln!("could not write {}: {}", path, error);
+            exit(1);
+        }
+    }
+}
+
+// mastermind tournament-compare <old results> <new results>
+fn compare_tournaments(args: &[String]) {
+    if 4 > args.len() {
+        eprintln!("usage: {} tournament-compare <old> <new>", args[0]);
+        exit(1);
+    }
+    let load = |path: &String| {
+        load_results(path).unwrap_or_else(|error| {
+            eprintln!("could not load {}: {}", path, error);
+            exit(1);
+        })
+    };
+    let (old, new) = (load(&args[2]), load(&args[3]));
+    match Tournament::compare(&old, &new) {
+        Ok((regressions, report)) => {
+            print!("{}", report);
+            if 0 < regressions {
+                exit(1);
+            }
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            exit(1);
+        }
+    }
+}
+
+// mastermind stats [player]
+fn show_stats(args: &[String]) {
+    let player = args.get(2).cloned().unwrap_or_else(get_player_name);
+    match load_records(&get_stats_path()) {
+        Ok(records) => print!("{}", show_player_stats(&records, &player)),
+        Err(error) => {
+            eprintln!("could not load the statistics: {}", error);
+            exit(1);
+        }
+    }
+}
+
+// mastermind leaderboard [board, e.g. 4x6-classic]
+fn show_players(args: &[String]) {
+    match load_records(&get_stats_path()) {
+        Ok(records) => print!(
+            "{}",
+            show_leaderboard(&records, args.get(2).map(String::as_str))
+        ),
+        Err(error) => {
+            eprintln!("could not load the statistics: {}", error);
+            exit(1);
+        }
+    }
+}
+
+// mastermind daily [salt] [game=SECONDS] [guess=SECONDS]
+fn play_daily(args: &[String]) {
+    let (options, args) = parse_play_options(args);
+    let day = daily::get_today();
+    let player = get_player_name();
+    let records = load_records(&get_stats_path()).unwrap_or_else(|error| {
+        eprintln!("could not load the statistics: {}", error);
+        exit(1);
+    });
+    if has_completed(&records, &player, &get_board_name("daily"), day) {
+        println!(
+            "{} already played the puzzle of {}, come back tomorrow",
+            player,
+            daily::get_date_string(day)
+        );
+        return;
+    }
+    let secret = daily::get_daily_secret(day, &daily::get_salt(args.get(2)));
+    let mut mm = Mastermind::new_with_state(secret);
+    // the attempt counts from the first guess on, even if the game is abandoned
+    let board = get_board_name("daily");
+    let started = GameRecord::new_lost(&player, &board, 0, Duration::ZERO);
+    save_record(&started);
+    let result = play_human(&mut mm, &options);
+    let finished = get_game_record("daily", mm.get_guesses().len(), &result);
+    if let Err(error) = replace_record(&get_stats_path(), &started, &finished) {
+        eprintln!("could not save the statistics: {}", error);
+    }
+    if result.solution.is_some() {
+        println!();
+        print!("{}", daily::get_share_grid(day, &mm.get_guesses()));
+    }
+}
+
+// mastermind puzzle [rows=N] [depth=N] [seed=N]
+fn play_puzzle(args: &[String]) {
+    let usage = || -> ! {
+        eprintln!("usage: {} puzzle [rows=N] [depth=N] [seed=N]", args[0]);
+        exit(1);
+    };
+    let (mut rows, mut depth) = (5, 3);
+    let mut seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_nanos() as u64);
+    for arg in args[2..].iter() {
+        let (key, number) = arg.split_once('=').unwrap_or_else(|| usage());
+        let number = number.parse::<usize>().unwrap_or_else(|_| usage());
+        match key {
+            "rows" => rows = number,
+            "depth" => depth = number,
+            "seed" => seed = number as u64,
+            _ => usage(),
+        }
+    }
+    let puzzle = generate_puzzle(seed, rows, depth).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    });
+    Colors::show_number_mapping();
+    println!(
+        "exactly one code fits these {} guesses, find it with one guess",
+        puzzle.get_rows().len()
+    );
+    print!("{}", puzzle);
+    let start = Instant::now();
+    let answer = loop {
+        let mut buf = String::new();
+        match std::io::stdin().read_line(&mut buf) {
+            Ok(0) => {
+                eprintln!("no answer entered");
+                exit(1);
+            }
+            Ok(_) => {}
+            Err(error) => {
+                eprintln!("{}", error);
+                exit(1);
+            }
+        }
+        match parse_guess(&buf) {
+            Some(values) => break values,
+            None => println!(
+                "a code has {} digits below {}",
+                mastermind_state::NUM_ELEMENTS,
+                Colors::len()
+            ),
+        }
+    };
+    println!("{}", show_solution(&puzzle, &answer));
+    print!("{}", difficulty::rate(puzzle.get_rows()));
+    if answer == puzzle.get_secret() {
+        record_game("puzzle", 1, start.elapsed());
+    } else {
+        record_lost_game("puzzle", 1, start.elapsed());
+    }
+}
+
+// mastermind puzzle-check <guess> <black,white> ...
+fn check_puzzle(args: &[String]) {
+    let usage = || -> ! {
+        eprintln!(
+            "usage: {} puzzle-check <guess> <black,white> [<guess> <black,white>...]",
+            args[0]
+        );
+        exit(1);
+    };
+    let words = &args[2..];
+    if words.is_empty() || !words.len().is_multiple_of(2) {
+        usage();
+    }
+    let rows: Vec<MastermindState> = words
+        .chunks_exact(2)
+        .map(
+            |pair| match (parse_guess(&pair[0]), parse_evaluation(&pair[1])) {
+                (Some(guess), Some(eval)) => MastermindState::new(guess, eval),
+                _ => usage(),
+            },
+        )
+        .collect();
+    let depth = get_reasoning_depth(&rows).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    });
+    let candidates = get_candidates(&rows);
+    print!("{}", difficulty::rate(&rows));
+    match depth {
+        Some(depth) => {
+            let solution = unpack(candidates.iter().next().unwrap());
+            println!(
+                "unique solution {}, reasoning depth {}",
+                get_string_from_guess(&solution),
+                depth
+            );
+        }
+        None => {
+            println!("{} codes fit, the puzzle is not unique", candidates.len());
+            exit(1);
+        }
+    }
+}
+
+fn run_benchmark() {
+    let (diff_time, build_time, table_time) = score_table::benchmark();
+    println!("scoring every pair with diff:  {:?}", diff_time);
+    println!("building the score table:      {:?}", build_time);
+    println!("scoring every pair with table: {:?}", table_time);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("words") => return play_words(&args),
+        Some("lies") => return play_lies(&args),
+        Some("static-guesses") => return show_static_guesses(),
+        Some("tree") => return export_tree(&args),
+        Some("make-book") => return make_book(&args),
+        Some("book") => return play_book(&args),
+        Some("opening") => return play_opening(&args),
+        Some("opening-search") => return show_opening_search(),
+        Some("hot-seat") => return play_two_players(&args),
+        Some("serve") => return run_server(&args),
+        Some("connect") => return run_client(&args),
+        Some("http") => return run_http_server(&args),
+        Some("bot") => return play_with_bot(&args),
+        Some("reference-bot") => return run_reference_bot(),
+        Some("tournament") => return run_tournament(&args),
+        Some("tournament-compare") => return compare_tournaments(&args),
+        Some("stats") => return show_stats(&args),
+        Some("leaderboard") => return show_players(&args),
+        Some("daily") => return play_daily(&args),
+        Some("practice") => return play_game(&args, true),
+        Some("puzzle") => return play_puzzle(&args),
+        Some("puzzle-check") => return check_puzzle(&args),
+        Some("bench") => return run_benchmark(),
+        _ => {}
+    }
+
+    play_game(&args, false);
+}
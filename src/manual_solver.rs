@@ -1,17 +1,69 @@
 use crate::colors::Colors;
+use crate::explain::Explainer;
+use crate::heat_map::{show_with_panel, HeatMap};
 use crate::mastermind::{GuessStatus, Mastermind};
-use crate::mastermind_state::{get_guess_from_string, Values};
-use crate::util::CURSOR_UP;
-use std::io::stdin;
+use crate::mastermind_state::{get_guess_from_string, MastermindState, Values};
+use crate::scoring_rule::ScoringRule;
+use crate::time_control::{parse_time_control, Clock, TimeControl};
+use crate::util::{CLEAR_LINE, CLEAR_SCREEN, CURSOR_UP, RESTORE_CURSOR, SAVE_CURSOR};
+use std::io::{stdin, stdout, Error, Write};
+use std::time::Duration;
 
-type InputFn = fn() -> Result<Values, std::io::Error>;
+pub type InputFn = fn() -> Result<Values, std::io::Error>;
 
-fn solve_with_input(mm: &mut Mastermind, input: InputFn) -> Values {
+// the clock is redrawn this often while waiting for a guess
+const TICK: Duration = Duration::from_secs(1);
+
+// how a human plays, given on the command line
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlayOptions {
+    pub time_control: TimeControl,
+    // practice games accept "undo" to take back the last guess
+    pub undo: bool,
+    // the remaining candidates per position and color next to the board
+    pub heat_map: bool,
+    // what the feedback of every guess rules out, only for black and white pegs
+    pub explain: bool,
+}
+
+// takes the time limits, "heat-map" and "explain" out of the arguments
+pub fn parse_play_options(args: &[String]) -> Result<(PlayOptions, Vec<String>), String> {
+    let (time_control, rest) = parse_time_control(args)?;
+    let heat_map = rest.iter().any(|arg| "heat-map" == arg);
+    let explain = rest.iter().any(|arg| "explain" == arg);
+    let rest = rest
+        .into_iter()
+        .filter(|arg| "heat-map" != arg && "explain" != arg)
+        .collect();
+    let options = PlayOptions {
+        time_control,
+        heat_map,
+        explain,
+        ..PlayOptions::default()
+    };
+    Ok((options, rest))
+}
+
+// how an interactive game ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameResult {
+    // None if the time ran out
+    pub solution: Option<Values>,
+    pub duration: Duration,
+    pub takebacks: usize,
+}
+
+fn solve_with_input<R: ScoringRule>(mm: &mut Mastermind<R>, input: InputFn) -> Values {
     Colors::show_number_mapping();
     let mut guess = input().unwrap();
     let mut solved = false;
     while !solved {
         print!("{}", CURSOR_UP);
+        if !mm.is_allowed(&guess) {
+            println!("not in the dictionary");
+            guess = input().unwrap();
+            continue;
+        }
         let status = mm.guess(guess);
         if GuessStatus::Success == status {
             solved = true;
@@ -22,13 +74,171 @@ fn solve_with_input(mm: &mut Mastermind, input: InputFn) -> Values {
     guess
 }
 
-fn get_guess() -> Result<Values, std::io::Error> {
+// the input waits at most the given time and returns None if no line came in
+pub fn play_with_clock<R, C, I>(
+    mm: &mut Mastermind<R>,
+    options: &PlayOptions,
+    clock: &C,
+    mut input: I,
+) -> Result<GameResult, Error>
+where
+    R: ScoringRule,
+    C: Clock,
+    I: FnMut(Option<Duration>) -> Result<Option<String>, Error>,
+{
+    let control = &options.time_control;
+    if options.heat_map {
+        // the whole board is redrawn for every guess instead
+        mm.set_verbose(false);
+    } else {
+        Colors::show_number_mapping();
+    }
+    let start = clock.elapsed();
+    let mut guess_start = start;
+    let mut result = GameResult {
+        solution: None,
+        duration: Duration::ZERO,
+        takebacks: 0,
+    };
+    // shown below the board before the next guess
+    let mut notice: Option<String> = None;
+    // lines on the screen for every guess, its row and the explanations
+    let mut rows: Vec<usize> = Vec::new();
+    let mut explainer = Explainer::new();
+    // the clock line is printed for a new guess and redrawn in place while waiting
+    let mut redraw = false;
+    loop {
+        let now = clock.elapsed();
+        result.duration = now - start;
+        if control.is_over(now - start, now - guess_start) {
+            return Ok(result);
+        }
+        if !redraw {
+            if options.heat_map {
+                show_board(mm);
+            }
+            if let Some(notice) = notice.take() {
+                println!("{}", notice);
+            }
+        }
+        if control.is_limited() {
+            let line = control.show_clock(now - start, now - guess_start);
+            if redraw {
+                print!(
+                    "{}{}{}\r{}{}",
+                    SAVE_CURSOR, CURSOR_UP, CLEAR_LINE, line, RESTORE_CURSOR
+                );
+            } else {
+                println!("{}", line);
+            }
+            stdout().flush()?;
+        }
+        let wait = control
+            .get_remaining(now - start, now - guess_start)
+            .map(|left| left.min(TICK));
+        let line = match input(wait)? {
+            Some(line) => line,
+            None => {
+                redraw = true;
+                continue;
+            }
+        };
+        redraw = false;
+        let now = clock.elapsed();
+        result.duration = now - start;
+        // a guess entered too late does not count either
+        if control.is_over(now - start, now - guess_start) {
+            return Ok(result);
+        }
+        if !options.heat_map {
+            // the row of the guess takes the place of the typed line and the clock
+            print!("{}{}", CURSOR_UP, CLEAR_LINE);
+            if control.is_limited() {
+                print!("{}{}", CURSOR_UP, CLEAR_LINE);
+            }
+        }
+        if "undo" == line.trim() {
+            if !options.undo {
+                notice = Some(String::from(
+                    "guesses can only be taken back in practice games",
+                ));
+            } else if mm.undo().is_some() {
+                if !options.heat_map {
+                    // erases the row of the last guess and its explanations
+                    for _ in 0..rows.pop().unwrap_or(1) {
+                        print!("{}{}", CURSOR_UP, CLEAR_LINE);
+                    }
+                }
+                explainer = Explainer::new_with_guesses(&get_classic_guesses(mm));
+                result.takebacks += 1;
+            } else {
+                notice = Some(String::from("nothing to undo"));
+            }
+            continue;
+        }
+        let guess = get_guess_from_string(line);
+        if !mm.is_allowed(&guess) {
+            notice = Some(String::from("not in the dictionary"));
+            continue;
+        }
+        if GuessStatus::Success == mm.guess(guess) {
+            if options.heat_map {
+                show_board(mm);
+            }
+            result.solution = Some(guess);
+            return Ok(result);
+        }
+        let mut lines = Vec::new();
+        if options.explain {
+            if let Some(state) = get_classic_guesses(mm).last() {
+                lines = explainer.add_guess(*state);
+            }
+        }
+        rows.push(1 + lines.len());
+        if !lines.is_empty() {
+            let lines: Vec<String> = lines.iter().map(|line| format!("  {}", line)).collect();
+            notice = Some(lines.join("\n"));
+        }
+        guess_start = now;
+    }
+}
+
+// the guesses with black and white pegs, empty for the other rules
+fn get_classic_guesses<R: ScoringRule>(mm: &Mastermind<R>) -> Vec<MastermindState> {
+    let rule = mm.get_rule();
+    mm.get_guesses()
+        .iter()
+        .filter_map(|state| {
+            let eval = rule.get_classic_feedback(&state.get_evaluation())?;
+            Some(MastermindState::new(state.get_values(), eval))
+        })
+        .collect()
+}
+
+// redraws the board with the heat map of the remaining candidates next to it
+fn show_board<R: ScoringRule>(mm: &Mastermind<R>) {
+    print!("{}", CLEAR_SCREEN);
+    Colors::show_number_mapping();
+    let board: Vec<String> = mm.get_guesses().iter().map(|s| s.to_string()).collect();
+    print!("{}", show_with_panel(&board, &HeatMap::new(mm).render()));
+}
+
+pub fn get_guess() -> Result<Values, std::io::Error> {
     let mut buf = String::new();
-    stdin().read_line(&mut buf)?;
+    if 0 == stdin().read_line(&mut buf)? {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "no more guesses",
+        ));
+    }
     Ok(get_guess_from_string(buf))
 }
 
 pub fn solve(mm: &mut Mastermind) -> Values {
+    solve_with_rule(mm)
+}
+
+pub fn solve_with_rule<R: ScoringRule>(mm: &mut Mastermind<R>) -> Values {
     solve_with_input(mm, get_guess)
 }
 
@@ -36,12 +246,19 @@ pub fn solve(mm: &mut Mastermind) -> Values {
 mod test {
     use crate::colors::Colors;
     use crate::manual_solver::solve;
-    use crate::manual_solver::solve_with_input;
+    use crate::manual_solver::{
+        get_classic_guesses, parse_play_options, play_with_clock, solve_with_input, GameResult,
+        PlayOptions,
+    };
     use crate::mastermind::Mastermind;
     use crate::mastermind_state::{get_guess_from_string, Values};
+    use crate::scoring_rule::BlackOnly;
     use crate::solver::test_utils::check_solution;
     use crate::solver::SolverFn;
+    use crate::time_control::{Clock, TimeControl};
+    use std::cell::Cell;
     use std::io::{Error, ErrorKind};
+    use std::time::Duration;
 
     #[test]
     fn solve_has_correct_type() {
@@ -58,24 +275,217 @@ mod test {
         check_solution(&values, &mm, &solution);
     }
 
-    fn get_blue_and_black_guess() -> Result<Values, Error> {
-        static mut NUM_QUERIES: u8 = 0;
-        unsafe {
-            NUM_QUERIES += 1;
-            if 1 == NUM_QUERIES {
-                Ok(get_guess_from_string(String::from("4444")))
-            } else {
-                Ok(get_guess_from_string(String::from("5555")))
-            }
-        }
-    }
-
     #[test]
     fn solve_with_incorrect_and_correct_guess() {
         let values = [Colors::Black; 4];
         let mut mm = Mastermind::new_with_state(values);
-        let solution = solve_with_input(&mut mm, get_blue_and_black_guess);
-        check_solution(&values, &mm, &solution);
+        let lines = [("4444", 1), ("5555", 1)];
+        let result = play_on(&mut mm, PlayOptions::default(), &lines).0.unwrap();
+        check_solution(&values, &mm, &result.solution.unwrap());
+        assert_eq!(2, mm.get_guesses().len());
+    }
+
+    struct FakeClock {
+        now: Cell<Duration>,
+    }
+
+    impl Clock for FakeClock {
+        fn elapsed(&self) -> Duration {
+            self.now.get()
+        }
+    }
+
+    const SECRET: Values = [Colors::Blue, Colors::Red, Colors::Green, Colors::Black];
+
+    fn seconds(seconds: u64) -> Option<Duration> {
+        Some(Duration::from_secs(seconds))
+    }
+
+    fn timed(game: Option<Duration>, guess: Option<Duration>) -> PlayOptions {
+        PlayOptions {
+            time_control: TimeControl { game, guess },
+            ..PlayOptions::default()
+        }
+    }
+
+    // every line is typed after the given number of seconds, returns the waits asked for
+    fn play(
+        options: PlayOptions,
+        lines: &[(&str, u64)],
+    ) -> (Result<GameResult, Error>, Vec<Option<Duration>>) {
+        play_on(&mut Mastermind::new_with_state(SECRET), options, lines)
+    }
+
+    fn play_on(
+        mm: &mut Mastermind,
+        options: PlayOptions,
+        lines: &[(&str, u64)],
+    ) -> (Result<GameResult, Error>, Vec<Option<Duration>>) {
+        let clock = FakeClock {
+            now: Cell::new(Duration::from_secs(1000)),
+        };
+        let mut lines = lines.iter();
+        let mut waits = Vec::new();
+        let result = play_with_clock(mm, &options, &clock, |wait| {
+            waits.push(wait);
+            let (line, time) = lines.next().ok_or(Error::from(ErrorKind::UnexpectedEof))?;
+            clock.now.set(clock.now.get() + Duration::from_secs(*time));
+            Ok(Some(String::from(*line)))
+        });
+        (result, waits)
+    }
+
+    #[test]
+    fn untimed_game_is_solved() {
+        let (result, waits) = play(PlayOptions::default(), &[("0000", 10), ("2015", 300)]);
+        assert_eq!(
+            GameResult {
+                solution: Some(SECRET),
+                duration: Duration::from_secs(310),
+                takebacks: 0,
+            },
+            result.unwrap()
+        );
+        assert_eq!(vec![None, None], waits);
+    }
+
+    #[test]
+    fn solved_in_time() {
+        let (result, waits) = play(timed(seconds(60), None), &[("0000", 10), ("2015", 15)]);
+        let result = result.unwrap();
+        assert_eq!(Some(SECRET), result.solution);
+        assert_eq!(Duration::from_secs(25), result.duration);
+        // the wait is cut into ticks, so the clock can be redrawn
+        assert_eq!(vec![seconds(1), seconds(1)], waits);
+    }
+
+    #[test]
+    fn game_clock_runs_out() {
+        let lines = [("0000", 30), ("0000", 30), ("2015", 1)];
+        let result = play(timed(seconds(60), None), &lines).0.unwrap();
+        assert_eq!(None, result.solution);
+        assert_eq!(Duration::from_secs(60), result.duration);
+    }
+
+    #[test]
+    fn guess_clock_runs_out() {
+        let lines = [("0000", 19), ("0000", 19), ("2015", 21)];
+        let result = play(timed(None, seconds(20)), &lines).0.unwrap();
+        assert_eq!(None, result.solution);
+        assert_eq!(Duration::from_secs(59), result.duration);
+    }
+
+    #[test]
+    fn no_guess_in_time_loses() {
+        let clock = FakeClock {
+            now: Cell::new(Duration::ZERO),
+        };
+        let mut mm = Mastermind::new_with_state(SECRET);
+        let mut waits = 0;
+        let result = play_with_clock(&mut mm, &timed(seconds(5), None), &clock, |wait| {
+            waits += 1;
+            clock.now.set(clock.now.get() + wait.unwrap());
+            Ok(None)
+        });
+        assert_eq!(None, result.unwrap().solution);
+        assert_eq!(5, waits);
+        assert!(mm.get_guesses().is_empty());
+    }
+
+    #[test]
+    fn practice_takes_back_guesses() {
+        let options = PlayOptions {
+            undo: true,
+            ..PlayOptions::default()
+        };
+        let lines = [("undo", 1), ("0000", 1), ("undo", 1), ("2015", 1)];
+        let result = play(options, &lines).0.unwrap();
+        assert_eq!(Some(SECRET), result.solution);
+        // the first undo had nothing to take back
+        assert_eq!(1, result.takebacks);
+    }
+
+    #[test]
+    fn only_practice_games_take_back_guesses() {
+        let lines = [("0000", 1), ("undo", 1), ("2015", 1)];
+        let result = play(PlayOptions::default(), &lines).0.unwrap();
+        assert_eq!(Some(SECRET), result.solution);
+        assert_eq!(0, result.takebacks);
+    }
+
+    #[test]
+    fn guess_outside_of_dictionary_is_asked_again() {
+        let mut mm = Mastermind::new_with_dictionary(vec![SECRET]);
+        let lines = [("0000", 1), ("2015", 1)];
+        let result = play_on(&mut mm, PlayOptions::default(), &lines).0.unwrap();
+        assert_eq!(Some(SECRET), result.solution);
+        assert_eq!(1, mm.get_guesses().len());
+    }
+
+    #[test]
+    fn input_ending_is_an_error() {
+        let (result, _) = play(PlayOptions::default(), &[("0000", 1)]);
+        assert_eq!(ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+    }
+
+    #[test]
+    fn explained_game_is_solved() {
+        let options = PlayOptions {
+            explain: true,
+            undo: true,
+            ..PlayOptions::default()
+        };
+        let lines = [
+            ("0000", 1),
+            ("1111", 1),
+            ("undo", 1),
+            ("3333", 1),
+            ("2015", 1),
+        ];
+        let result = play(options, &lines).0.unwrap();
+        assert_eq!(Some(SECRET), result.solution);
+        assert_eq!(1, result.takebacks);
+    }
+
+    #[test]
+    fn only_black_and_white_pegs_are_explained() {
+        let mut mm = Mastermind::new_with_state(SECRET);
+        mm.set_verbose(false);
+        mm.guess([Colors::Red; 4]);
+        assert_eq!(mm.get_guesses(), get_classic_guesses(&mm));
+        let mut mm = Mastermind::new_with_state_and_rule(SECRET, BlackOnly);
+        mm.set_verbose(false);
+        mm.guess([Colors::Red; 4]);
+        assert!(get_classic_guesses(&mm).is_empty());
+    }
+
+    #[test]
+    fn heat_map_game_is_solved() {
+        let options = PlayOptions {
+            heat_map: true,
+            undo: true,
+            ..PlayOptions::default()
+        };
+        let lines = [("0000", 1), ("undo", 1), ("1111", 1), ("2015", 1)];
+        let result = play(options, &lines).0.unwrap();
+        assert_eq!(Some(SECRET), result.solution);
+        assert_eq!(1, result.takebacks);
+    }
+
+    #[test]
+    fn play_options_are_taken_out_of_the_arguments() {
+        let args =
+            |text: &str| -> Vec<String> { text.split_whitespace().map(String::from).collect() };
+        let (options, rest) =
+            parse_play_options(&args("mastermind heat-map wordle explain game=60")).unwrap();
+        assert!(options.heat_map);
+        assert!(options.explain);
+        assert!(!options.undo);
+        assert_eq!(Some(Duration::from_secs(60)), options.time_control.game);
+        assert_eq!(args("mastermind wordle"), rest);
+        let (options, _) = parse_play_options(&args("mastermind")).unwrap();
+        assert_eq!(PlayOptions::default(), options);
+        assert!(parse_play_options(&args("mastermind guess=x")).is_err());
     }
 
     #[test]
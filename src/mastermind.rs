@@ -1,63 +1,170 @@
 use crate::evaluation::Evaluation;
-use crate::mastermind_state::{MastermindState, Values, NUM_ELEMENTS};
-use std::fmt::{Display, Error, Formatter};
+use crate::lies::Liar;
+use crate::mastermind_state::{get_all_values, MastermindState, Values};
+use crate::scoring_rule::{Classic, ScoringRule};
+use crate::util::get_random_number_usize;
+use std::fmt::{Debug, Display, Error, Formatter};
 use std::vec::Vec;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub enum GuessStatus {
+pub enum GuessStatus<F = Evaluation> {
     Success,
-    Incorrect(Evaluation),
+    Incorrect(F),
+    // the guess is not in the dictionary and was not played
+    Rejected,
 }
 
-impl Display for GuessStatus {
+impl<F: Debug> Display for GuessStatus<F> {
     fn fmt(&self, format: &mut Formatter) -> Result<(), Error> {
         write!(format, "{:?}", self)
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct Mastermind {
+pub struct Mastermind<R: ScoringRule = Classic> {
     initial: MastermindState,
-    guesses: Vec<MastermindState>,
+    guesses: Vec<MastermindState<R::Feedback>>,
+    rule: R,
+    // restricts secrets and guesses if present
+    dictionary: Option<Vec<Values>>,
+    // the codemaker lies if present
+    liar: Option<Liar>,
+    // prints every guess with its feedback
+    verbose: bool,
 }
 
 impl Mastermind {
     pub fn new() -> Self {
+        Mastermind::new_with_rule(Classic)
+    }
+
+    pub fn new_with_state(values: Values) -> Self {
+        Mastermind::new_with_state_and_rule(values, Classic)
+    }
+
+    pub fn new_with_dictionary(dictionary: Vec<Values>) -> Self {
+        assert!(!dictionary.is_empty());
+        let secret = dictionary[get_random_number_usize(dictionary.len())];
+        Mastermind {
+            initial: MastermindState::new_initial(secret),
+            guesses: Vec::new(),
+            rule: Classic,
+            dictionary: Some(dictionary),
+            liar: None,
+            verbose: true,
+        }
+    }
+}
+
+impl<R: ScoringRule> Mastermind<R> {
+    pub fn new_with_rule(rule: R) -> Self {
         Mastermind {
             initial: MastermindState::new_random_state(),
-            guesses: Vec::<MastermindState>::new(),
+            guesses: Vec::new(),
+            rule,
+            dictionary: None,
+            liar: None,
+            verbose: true,
         }
     }
 
-    pub fn new_with_state(values: Values) -> Self {
+    pub fn new_with_state_and_rule(values: Values, rule: R) -> Self {
         Mastermind {
             initial: MastermindState::new_initial(values),
-            guesses: Vec::<MastermindState>::new(),
+            guesses: Vec::new(),
+            rule,
+            dictionary: None,
+            liar: None,
+            verbose: true,
         }
     }
 
-    pub fn guess(&mut self, values: Values) -> GuessStatus {
-        let mmstate = self.initial.new_diff_state(values);
+    pub fn new_with_liar(rule: R, liar: Liar) -> Self {
+        Mastermind {
+            initial: MastermindState::new_random_state(),
+            guesses: Vec::new(),
+            rule,
+            dictionary: None,
+            liar: Some(liar),
+            verbose: true,
+        }
+    }
+
+    pub fn guess(&mut self, values: Values) -> GuessStatus<R::Feedback> {
+        if !self.is_allowed(&values) {
+            return GuessStatus::Rejected;
+        }
+        let mut eval = self.rule.score(&self.initial.get_values(), &values);
+        let solved = self.initial.are_values_equal(&values);
+        if !solved && self.liar.is_some() {
+            let codes = self.get_allowed_guesses();
+            if let Some(liar) = self.liar.as_mut() {
+                eval = liar.answer(&self.rule, &codes, &self.guesses, &values, eval);
+            }
+        }
+        let mmstate = MastermindState::new(values, eval);
         self.guesses.push(mmstate);
-        println!("{}", mmstate);
-        let diff = mmstate.get_evaluation();
-        if diff.get_correct_match() as usize == NUM_ELEMENTS {
+        if self.verbose {
+            println!("{}", mmstate);
+        }
+        if solved {
             GuessStatus::Success
         } else {
-            GuessStatus::Incorrect(diff)
+            GuessStatus::Incorrect(eval)
         }
     }
 
+    // takes back the last guess, a liar would give away its lies
+    pub fn undo(&mut self) -> Option<MastermindState<R::Feedback>> {
+        if self.liar.is_some() {
+            return None;
+        }
+        self.guesses.pop()
+    }
+
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+    }
+
     pub fn get_initial(&self) -> MastermindState {
         self.initial
     }
 
-    pub fn get_guesses(&self) -> Vec<MastermindState> {
+    pub fn get_guesses(&self) -> Vec<MastermindState<R::Feedback>> {
         self.guesses.clone()
     }
+
+    pub fn get_rule(&self) -> &R {
+        &self.rule
+    }
+
+    pub fn get_max_lies(&self) -> u8 {
+        self.liar.as_ref().map_or(0, Liar::get_max_lies)
+    }
+
+    // indices of the guesses which got a wrong feedback
+    pub fn get_lies(&self) -> Vec<usize> {
+        self.liar
+            .as_ref()
+            .map_or(Vec::new(), |liar| liar.get_lies().to_vec())
+    }
+
+    pub fn is_allowed(&self, values: &Values) -> bool {
+        match &self.dictionary {
+            Some(dictionary) => dictionary.contains(values),
+            None => true,
+        }
+    }
+
+    pub fn get_allowed_guesses(&self) -> Vec<Values> {
+        match &self.dictionary {
+            Some(dictionary) => dictionary.clone(),
+            None => get_all_values(),
+        }
+    }
 }
 
-impl Display for Mastermind {
+impl<R: ScoringRule> Display for Mastermind<R> {
     fn fmt(&self, format: &mut Formatter) -> Result<(), Error> {
         write!(format, "{}", self.initial)
     }
@@ -65,14 +172,18 @@ impl Display for Mastermind {
 
 #[cfg(test)]
 mod test {
+    use crate::colors::Colors;
     use crate::evaluation::Evaluation;
+    use crate::lies::{Liar, LieMode};
     use crate::mastermind::GuessStatus;
     use crate::mastermind::Mastermind;
     use crate::mastermind_state::MastermindState;
+    use crate::position_evaluation::PositionEvaluation;
+    use crate::scoring_rule::{BlackOnly, Classic, Positional};
 
     #[test]
     fn guess_status_display() {
-        assert_eq!("Success", format!("{}", GuessStatus::Success));
+        assert_eq!("Success", format!("{}", GuessStatus::<Evaluation>::Success));
         assert_eq!(
             "Incorrect(Evaluation { correct_match: 2, color_present: 1 })",
             format!("{}", GuessStatus::Incorrect(Evaluation::new(2, 1)))
@@ -114,4 +225,119 @@ mod test {
         let eval = mm.get_initial().diff(&state.get_values());
         assert_eq!(GuessStatus::Incorrect(eval), status);
     }
+
+    #[test]
+    fn guess_uses_scoring_rule() {
+        let mut mm = Mastermind::new_with_state_and_rule([Colors::Red; 4], BlackOnly);
+        let status = mm.guess([Colors::Red, Colors::Green, Colors::Green, Colors::Green]);
+        assert_eq!(GuessStatus::Incorrect(Evaluation::new(1, 0)), status);
+        assert_eq!(GuessStatus::Success, mm.guess([Colors::Red; 4]));
+    }
+
+    #[test]
+    fn guess_with_positional_rule() {
+        let mut mm = Mastermind::new_with_state_and_rule([Colors::Red; 4], Positional);
+        let status = mm.guess([Colors::Red, Colors::Green, Colors::Red, Colors::Green]);
+        assert_eq!(
+            GuessStatus::Incorrect(PositionEvaluation::new([true, false, true, false])),
+            status
+        );
+        assert_eq!(1, mm.get_guesses().len());
+    }
+
+    #[test]
+    fn undo_restores_the_previous_position() {
+        let mut mm = Mastermind::new_with_state([Colors::Red; 4]);
+        assert_eq!(None, mm.undo());
+        mm.guess([Colors::Green; 4]);
+        let before = mm.clone();
+        let last = mm.guess([Colors::Red, Colors::Blue, Colors::Blue, Colors::Blue]);
+        assert_eq!(GuessStatus::Incorrect(Evaluation::new(1, 0)), last);
+        assert_eq!(
+            Some(MastermindState::new(
+                [Colors::Red, Colors::Blue, Colors::Blue, Colors::Blue],
+                Evaluation::new(1, 0)
+            )),
+            mm.undo()
+        );
+        assert_eq!(before, mm);
+    }
+
+    #[test]
+    fn liar_does_not_allow_undo() {
+        let mut mm = Mastermind::new_with_liar(Classic, Liar::new(LieMode::Random, 1));
+        mm.set_verbose(false);
+        let guess = if mm.get_initial().get_values() == [Colors::Red; 4] {
+            [Colors::Green; 4]
+        } else {
+            [Colors::Red; 4]
+        };
+        mm.guess(guess);
+        assert_eq!(None, mm.undo());
+        assert_eq!(1, mm.get_guesses().len());
+    }
+
+    #[test]
+    fn new_with_dictionary_picks_secret_from_dictionary() {
+        let dictionary = vec![
+            [Colors::Black, Colors::Red, Colors::Blue, Colors::White],
+            [Colors::Green, Colors::White, Colors::Red, Colors::Yellow],
+        ];
+        let mm = Mastermind::new_with_dictionary(dictionary.clone());
+        assert!(dictionary.contains(&mm.get_initial().get_values()));
+        assert_eq!(dictionary, mm.get_allowed_guesses());
+        assert!(mm.is_allowed(&dictionary[1]));
+        assert!(!mm.is_allowed(&[Colors::Red; 4]));
+    }
+
+    #[test]
+    fn without_dictionary_everything_is_allowed() {
+        let mm = Mastermind::new();
+        assert!(mm.is_allowed(&[Colors::Red; 4]));
+        assert_eq!(1296, mm.get_allowed_guesses().len());
+    }
+
+    #[test]
+    fn guess_outside_of_dictionary_is_rejected() {
+        let mut mm = Mastermind::new_with_dictionary(vec![[Colors::Black; 4]]);
+        assert_eq!(GuessStatus::Rejected, mm.guess([Colors::Red; 4]));
+        assert!(mm.get_guesses().is_empty());
+        assert_eq!(GuessStatus::Success, mm.guess([Colors::Black; 4]));
+    }
+
+    #[test]
+    fn honest_codemaker_tells_no_lies() {
+        let mut mm = Mastermind::new();
+        mm.guess([Colors::Red; 4]);
+        assert_eq!(0, mm.get_max_lies());
+        assert!(mm.get_lies().is_empty());
+    }
+
+    #[test]
+    fn liar_lies_at_most_max_lies_times() {
+        let mut mm = Mastermind::new_with_liar(Classic, Liar::new(LieMode::Random, 2));
+        assert_eq!(2, mm.get_max_lies());
+        let secret = mm.get_initial().get_values();
+        let mut guess = [Colors::Red; 4];
+        for _ in 0..20 {
+            if guess == secret {
+                guess[0] = Colors::Green;
+            }
+            mm.guess(guess);
+        }
+        let lies = mm.get_lies();
+        assert!(lies.len() <= 2);
+        for (i, state) in mm.get_guesses().iter().enumerate() {
+            let truth = mm.get_initial().diff(&state.get_values());
+            assert_eq!(lies.contains(&i), truth != state.get_evaluation());
+        }
+    }
+
+    #[test]
+    fn liar_cannot_deny_success() {
+        let mut mm = Mastermind::new_with_liar(Classic, Liar::new(LieMode::Adversarial, 3));
+        let secret = mm.get_initial().get_values();
+        assert_eq!(GuessStatus::Success, mm.guess(secret));
+        assert!(mm.get_lies().is_empty());
+    }
 }
@@ -1,3 +1,4 @@
+use crate::code::{get_num_codes, unpack, Code};
 use crate::colors::Colors;
 use crate::evaluation::Evaluation;
 use crate::util::get_random_number_u8;
@@ -25,10 +26,41 @@ pub fn get_guess_from_string(buf: String) -> Values {
     result
 }
 
+// unlike get_guess_from_string every position needs a valid digit
+pub fn parse_guess(text: &str) -> Option<Values> {
+    let text = text.trim();
+    if NUM_ELEMENTS != text.len() {
+        return None;
+    }
+    let mut values: Values = [Colors::Red; NUM_ELEMENTS];
+    for (val, c) in values.iter_mut().zip(text.bytes()) {
+        if c < b'0' || c >= b'0' + Colors::len() {
+            return None;
+        }
+        *val = Colors::from(c - b'0');
+    }
+    Some(values)
+}
+
+// inverse of get_guess_from_string
+pub fn get_string_from_guess(values: &Values) -> String {
+    values
+        .iter()
+        .map(|val| char::from(b'0' + *val as u8))
+        .collect()
+}
+
+// every possible code, the first position changes slowest
+pub fn get_all_values() -> Vec<Values> {
+    (0..get_num_codes())
+        .map(|code| unpack(code as Code))
+        .collect()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct MastermindState {
+pub struct MastermindState<F = Evaluation> {
     values: Values,
-    eval: Evaluation,
+    eval: F,
 }
 
 impl MastermindState {
@@ -40,10 +72,6 @@ impl MastermindState {
         MastermindState::new(values, Evaluation::new(0, 0))
     }
 
-    pub fn new(values: Values, eval: Evaluation) -> Self {
-        MastermindState { values, eval }
-    }
-
     pub fn new_initial(values: Values) -> Self {
         MastermindState {
             values,
@@ -51,15 +79,6 @@ impl MastermindState {
         }
     }
 
-    pub fn new_diff_state(&self, values: Values) -> MastermindState {
-        let eval = MastermindState::diff(self, &values);
-        MastermindState::new(values, eval)
-    }
-
-    pub fn are_values_equal(&self, rhs: &Values) -> bool {
-        self.values == *rhs
-    }
-
     pub fn diff(&self, guess: &Values) -> Evaluation {
         let mut correct_matches: u8 = 0;
         let mut color_present: u8 = 0;
@@ -96,8 +115,18 @@ impl MastermindState {
         assert!((correct_matches + color_present) as usize <= self.values.len());
         Evaluation::new(correct_matches, color_present)
     }
+}
 
-    pub fn get_evaluation(&self) -> Evaluation {
+impl<F: Copy> MastermindState<F> {
+    pub fn new(values: Values, eval: F) -> Self {
+        MastermindState { values, eval }
+    }
+
+    pub fn are_values_equal(&self, rhs: &Values) -> bool {
+        self.values == *rhs
+    }
+
+    pub fn get_evaluation(&self) -> F {
         self.eval
     }
 
@@ -106,7 +135,7 @@ impl MastermindState {
     }
 }
 
-impl Display for MastermindState {
+impl<F: Display> Display for MastermindState<F> {
     fn fmt(&self, format: &mut Formatter) -> Result<(), Error> {
         for v in &self.values {
             write!(format, "{}", v)?;
@@ -119,7 +148,11 @@ impl Display for MastermindState {
 mod test {
     use crate::colors::Colors;
     use crate::evaluation::Evaluation;
-    use crate::mastermind_state::{get_guess_from_string, MastermindState, NUM_ELEMENTS};
+    use crate::mastermind_state::{
+        get_all_values, get_guess_from_string, get_string_from_guess, parse_guess, MastermindState,
+        NUM_ELEMENTS,
+    };
+    use std::collections::HashSet;
 
     #[test]
     fn create_mastermind_state() {
@@ -308,4 +341,39 @@ mod test {
             [Colors::Yellow, Colors::Black, Colors::Blue, Colors::Blue]
         );
     }
+
+    #[test]
+    fn get_string_from_guess_is_inverse_of_get_guess_from_string() {
+        let values = [Colors::Yellow, Colors::Blue, Colors::Green, Colors::Black];
+        assert_eq!("3215", get_string_from_guess(&values));
+        assert_eq!(
+            values,
+            get_guess_from_string(get_string_from_guess(&values))
+        );
+    }
+
+    #[test]
+    fn parse_guess_needs_every_digit() {
+        assert_eq!(
+            Some([Colors::Red, Colors::Black, Colors::Green, Colors::Red]),
+            parse_guess("0510\n")
+        );
+        assert_eq!(None, parse_guess("051"));
+        assert_eq!(None, parse_guess("0516"));
+        assert_eq!(None, parse_guess("05a1"));
+    }
+
+    #[test]
+    fn get_all_values_enumerates_every_code_once() {
+        let all_values = get_all_values();
+        assert_eq!(1296, all_values.len());
+        assert_eq!([Colors::Red; 4], all_values[0]);
+        assert_eq!(
+            [Colors::Red, Colors::Red, Colors::Red, Colors::Green],
+            all_values[1]
+        );
+        assert_eq!([Colors::Black; 4], all_values[1295]);
+        let unique: HashSet<_> = all_values.iter().collect();
+        assert_eq!(all_values.len(), unique.len());
+    }
 }
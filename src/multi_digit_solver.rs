@@ -12,7 +12,7 @@ fn solve_colors(mm: &mut Mastermind) -> Values {
         let guess: Values = [*c; NUM_ELEMENTS];
         let status = mm.guess(guess);
         match status {
-            GuessStatus::Success => {
+            GuessStatus::Success | GuessStatus::Rejected => {
                 return [*c; NUM_ELEMENTS];
             }
             GuessStatus::Incorrect(s) => {
@@ -40,7 +40,9 @@ fn are_all_colors_equal(values: &Values) -> bool {
 
 type PossibleColorsT = [HashSet<Colors>; NUM_ELEMENTS];
 
-struct PossibleColors {
+// colors which are still possible in every position
+#[derive(Debug, Clone, PartialEq)]
+pub struct PossibleColors {
     colors: PossibleColorsT,
 }
 
@@ -57,7 +59,7 @@ impl PossibleColors {
         PossibleColors { colors: result }
     }
 
-    fn reduce_colors(&mut self, values: &Values, eval: &Evaluation) {
+    pub fn reduce_colors(&mut self, values: &Values, eval: &Evaluation) {
         if 0 == eval.get_correct_match() {
             for (i, &value) in values.iter().enumerate() {
                 self.colors[i].remove(&value);
@@ -150,6 +152,63 @@ impl PossibleColors {
         }
         used_colors.len()
     }
+
+    // nothing is known yet, every color is possible everywhere
+    pub fn new_with_all_colors() -> PossibleColors {
+        let mut result: PossibleColorsT = Default::default();
+        for r in result.iter_mut() {
+            *r = Colors::iter().copied().collect();
+        }
+        PossibleColors { colors: result }
+    }
+
+    pub fn get_colors(&self, position: usize) -> &HashSet<Colors> {
+        &self.colors[position]
+    }
+
+    // a guess without any pegs has none of its colors anywhere
+    pub fn remove_absent_colors(&mut self, values: &Values, eval: &Evaluation) {
+        if 0 == eval.get_correct_match() + eval.get_color_present() {
+            for colors in self.colors.iter_mut() {
+                for value in values {
+                    colors.remove(value);
+                }
+            }
+        }
+    }
+
+    // two guesses which only differ by swapping two colors tell something about both positions
+    pub fn reduce_colors_with_swap(
+        &mut self,
+        values: &Values,
+        eval: &Evaluation,
+        old_values: &Values,
+        old_eval: &Evaluation,
+    ) {
+        let (&better_values, &better_eval, &worse_values, &worse_eval) =
+            PossibleColors::sort(values, eval, old_values, old_eval);
+        let diff = better_eval.get_correct_match() - worse_eval.get_correct_match();
+        let swapped: Vec<usize> = (0..values.len())
+            .filter(|&i| values[i] != old_values[i])
+            .collect();
+        // without a gain the colors could also be in both positions
+        if 2 != swapped.len() || 0 == diff {
+            return;
+        }
+        let (i, j) = (swapped[0], swapped[1]);
+        if values[i] != old_values[j] || values[j] != old_values[i] {
+            return;
+        }
+        for &k in [i, j].iter() {
+            if 2 == diff {
+                // only narrows down what earlier guesses left, an empty set is a contradiction
+                self.colors[k].retain(|c| *c == better_values[k]);
+            } else {
+                let action = PossibleColors::create_actions(diff);
+                action(&better_values[k], &worse_values[k], &mut self.colors[k]);
+            }
+        }
+    }
 }
 
 pub fn solve(mm: &mut Mastermind) -> Values {
@@ -163,7 +222,7 @@ pub fn solve(mm: &mut Mastermind) -> Values {
     let mut shift_loop = true;
     while shift_loop {
         match mm.guess(result) {
-            GuessStatus::Success => return result,
+            GuessStatus::Success | GuessStatus::Rejected => return result,
             GuessStatus::Incorrect(e) => {
                 eval = e;
                 possible_colors.reduce_colors(&result, &eval);
@@ -192,7 +251,7 @@ pub fn solve(mm: &mut Mastermind) -> Values {
                     continue 'second_pos;
                 }
                 match mm.guess(current_guess) {
-                    GuessStatus::Success => return current_guess,
+                    GuessStatus::Success | GuessStatus::Rejected => return current_guess,
                     GuessStatus::Incorrect(e) => {
                         possible_colors.reduce_colors_with_previous_state(
                             &current_guess,
@@ -0,0 +1,244 @@
+// line based protocol, every message is one line of ASCII text
+//
+// client to server:
+//   NEW            starts a new game with a random secret code
+//   GUESS <code>   guesses a code given as digits, e.g. "GUESS 0123"
+//   QUIT           ends the connection
+//
+// server to client:
+//   STARTED                  a new game is running
+//   FEEDBACK <black> <white> the guess was wrong, e.g. "FEEDBACK 1 2"
+//   SOLVED <guesses>         game over, the guess was the secret code
+//   BYE                      answer to QUIT, the server closes the connection
+//   ERROR <message>          the line was not understood, the game goes on
+use crate::evaluation::Evaluation;
+use crate::manual_solver::InputFn;
+use crate::mastermind::{GuessStatus, Mastermind};
+use crate::mastermind_state::{
+    get_string_from_guess, parse_guess, MastermindState, Values, NUM_ELEMENTS,
+};
+use std::io::{BufRead, BufReader, Error, ErrorKind, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+// picks the secret code of every new game
+pub type SecretFn = fn() -> Values;
+
+pub fn new_random_secret() -> Values {
+    MastermindState::new_random_state().get_values()
+}
+
+// answers one request, None closes the connection
+fn answer(line: &str, game: &mut Option<Mastermind>, secret: SecretFn) -> Option<String> {
+    let mut words = line.split_whitespace();
+    let answer = match (words.next(), words.next(), words.next()) {
+        (Some("NEW"), None, _) => {
+            let mut mm = Mastermind::new_with_state(secret());
+            mm.set_verbose(false);
+            *game = Some(mm);
+            String::from("STARTED")
+        }
+        (Some("GUESS"), Some(code), None) => match (game.as_mut(), parse_guess(code)) {
+            (None, _) => String::from("ERROR no game running, send NEW"),
+            (_, None) => format!("ERROR invalid code {}", code),
+            (Some(mm), Some(values)) => match mm.guess(values) {
+                GuessStatus::Success => {
+                    let answer = format!("SOLVED {}", mm.get_guesses().len());
+                    *game = None;
+                    answer
+                }
+                GuessStatus::Incorrect(e) => format!(
+                    "FEEDBACK {} {}",
+                    e.get_correct_match(),
+                    e.get_color_present()
+                ),
+                GuessStatus::Rejected => format!("ERROR code {} is not allowed", code),
+            },
+        },
+        (Some("QUIT"), None, _) => return None,
+        _ => format!("ERROR unknown command {}", line.trim()),
+    };
+    Some(answer)
+}
+
+// plays games with one client until it quits or disconnects
+pub fn handle_client<T: BufRead, W: Write>(
+    input: T,
+    mut output: W,
+    secret: SecretFn,
+) -> Result<(), Error> {
+    let mut game = None;
+    for line in input.lines() {
+        match answer(&line?, &mut game, secret) {
+            Some(answer) => writeln!(output, "{}", answer)?,
+            None => {
+                writeln!(output, "BYE")?;
+                break;
+            }
+        }
+        output.flush()?;
+    }
+    Ok(())
+}
+
+// every client is served by its own thread
+pub fn serve(listener: TcpListener, secret: SecretFn) -> Result<(), Error> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        thread::spawn(move || {
+            let input = BufReader::new(stream.try_clone()?);
+            handle_client(input, stream, secret)
+        });
+    }
+    Ok(())
+}
+
+fn read_answer<T: BufRead>(input: &mut T) -> Result<String, Error> {
+    let mut line = String::new();
+    if 0 == input.read_line(&mut line)? {
+        return Err(Error::new(
+            ErrorKind::UnexpectedEof,
+            "server closed the connection",
+        ));
+    }
+    Ok(line.trim().to_string())
+}
+
+fn parse_feedback(line: &str) -> Option<Evaluation> {
+    let mut words = line.split_whitespace().skip(1);
+    let correct_match = words.next()?.parse::<u8>().ok()?;
+    let color_present = words.next()?.parse::<u8>().ok()?;
+    Some(Evaluation::new(correct_match, color_present))
+}
+
+// plays one game on the server, returns the number of guesses
+pub fn play_remote<T: BufRead, W: Write>(
+    mut input: T,
+    mut output: W,
+    guess: InputFn,
+) -> Result<usize, Error> {
+    writeln!(output, "NEW")?;
+    output.flush()?;
+    let answer = read_answer(&mut input)?;
+    if "STARTED" != answer {
+        return Err(Error::new(ErrorKind::InvalidData, answer));
+    }
+    loop {
+        let values = guess()?;
+        writeln!(output, "GUESS {}", get_string_from_guess(&values))?;
+        output.flush()?;
+        let answer = read_answer(&mut input)?;
+        if let Some(steps) = answer.strip_prefix("SOLVED") {
+            println!(
+                "{}",
+                MastermindState::new(values, Evaluation::new(NUM_ELEMENTS as u8, 0))
+            );
+            writeln!(output, "QUIT")?;
+            output.flush()?;
+            return steps
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| Error::new(ErrorKind::InvalidData, answer.clone()));
+        }
+        match parse_feedback(&answer) {
+            Some(eval) if answer.starts_with("FEEDBACK") => {
+                println!("{}", MastermindState::new(values, eval))
+            }
+            _ => println!("{}", answer),
+        }
+    }
+}
+
+pub fn connect(address: &str, guess: InputFn) -> Result<usize, Error> {
+    let stream = TcpStream::connect(address)?;
+    play_remote(BufReader::new(stream.try_clone()?), stream, guess)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::Colors;
+    use crate::mastermind_state::Values;
+    use crate::network::{connect, handle_client, serve};
+    use std::io::{BufRead, BufReader, Error, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    fn secret() -> Values {
+        [Colors::Red, Colors::Green, Colors::Blue, Colors::Yellow]
+    }
+
+    fn answers(requests: &str) -> Vec<String> {
+        let mut output = Vec::new();
+        handle_client(requests.as_bytes(), &mut output, secret).unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn game_over_the_protocol() {
+        assert_eq!(
+            vec!["STARTED", "FEEDBACK 1 0", "FEEDBACK 0 4", "SOLVED 3", "BYE"],
+            answers("NEW\nGUESS 0055\nGUESS 1032\nGUESS 0123\nQUIT\nGUESS 0123\n")
+        );
+    }
+
+    #[test]
+    fn errors_keep_the_connection() {
+        assert_eq!(
+            vec![
+                "ERROR no game running, send NEW",
+                "STARTED",
+                "ERROR invalid code 01234",
+                "ERROR unknown command HELLO",
+                "ERROR unknown command GUESS",
+                "SOLVED 1",
+                "ERROR no game running, send NEW",
+            ],
+            answers("GUESS 0123\nNEW\nGUESS 01234\nHELLO\nGUESS\nGUESS 0123\nGUESS 0123\n")
+        );
+    }
+
+    fn start_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || serve(listener, secret));
+        address
+    }
+
+    #[test]
+    fn serve_over_loopback() {
+        let address = start_server();
+        let mut stream = TcpStream::connect(&address).unwrap();
+        let mut input = BufReader::new(stream.try_clone().unwrap());
+        let mut line = String::new();
+        for (request, expected) in [("NEW", "STARTED"), ("GUESS 0123", "SOLVED 1")].iter() {
+            writeln!(stream, "{}", request).unwrap();
+            line.clear();
+            input.read_line(&mut line).unwrap();
+            assert_eq!(*expected, line.trim());
+        }
+    }
+
+    fn guess_secret() -> Result<Values, Error> {
+        Ok(secret())
+    }
+
+    #[test]
+    fn connect_over_loopback() {
+        let address = start_server();
+        assert_eq!(1, connect(&address, guess_secret).unwrap());
+        // several clients at the same time
+        let clients: Vec<_> = (0..4)
+            .map(|_| {
+                let address = address.clone();
+                thread::spawn(move || connect(&address, guess_secret).unwrap())
+            })
+            .collect();
+        for client in clients {
+            assert_eq!(1, client.join().unwrap());
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use crate::colors::Colors;
+use crate::mastermind::Mastermind;
+use crate::mastermind_state::{get_all_values, parse_guess, Values, NUM_ELEMENTS};
+use crate::state_of_the_art_solver::solve_with_opening;
+
+// either a code like "0011" or a pattern like "AABB", the n-th new letter is the n-th color
+pub fn parse_opening(text: &str) -> Option<Values> {
+    if NUM_ELEMENTS != text.len() {
+        return None;
+    }
+    if let Some(values) = parse_guess(text) {
+        return Some(values);
+    }
+    let mut letters: Vec<u8> = Vec::new();
+    let mut values: Values = [Colors::Red; NUM_ELEMENTS];
+    for (val, c) in values.iter_mut().zip(text.bytes()) {
+        if !c.is_ascii_uppercase() {
+            return None;
+        }
+        let index = match letters.iter().position(|letter| *letter == c) {
+            Some(index) => index,
+            None => {
+                letters.push(c);
+                letters.len() - 1
+            }
+        };
+        if index >= Colors::len() as usize {
+            return None;
+        }
+        *val = Colors::from(index as u8);
+    }
+    Some(values)
+}
+
+// inverse of parse_opening for codes which use the colors in order of appearance
+pub fn get_pattern_string(values: &Values) -> String {
+    values
+        .iter()
+        .map(|val| char::from(b'A' + *val as u8))
+        .collect()
+}
+
+// every way to split the positions into groups of the same color, e.g. AAAA, AAAB, ..., ABCD
+pub fn get_opening_patterns() -> Vec<Values> {
+    let mut patterns = Vec::new();
+    let mut pattern: Values = [Colors::Red; NUM_ELEMENTS];
+    add_patterns(&mut patterns, &mut pattern, 1, 1);
+    patterns
+}
+
+fn add_patterns(patterns: &mut Vec<Values>, pattern: &mut Values, position: usize, used: u8) {
+    if NUM_ELEMENTS == position {
+        patterns.push(*pattern);
+        return;
+    }
+    for color in 0..Colors::len().min(used + 1) {
+        pattern[position] = Colors::from(color);
+        add_patterns(patterns, pattern, position + 1, used.max(color + 1));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpeningResult {
+    pub opening: Values,
+    pub worst_case: usize,
+    pub average: f64,
+}
+
+// plays state_of_the_art_solver with this opening against every secret
+pub fn evaluate_opening(opening: Values) -> OpeningResult {
+    let mut worst_case = 0;
+    let mut total = 0;
+    let secrets = get_all_values();
+    for secret in secrets.iter() {
+        let mut mm = Mastermind::new_with_state(*secret);
+        mm.set_verbose(false);
+        solve_with_opening(&mut mm, opening);
+        let steps = mm.get_guesses().len();
+        worst_case = worst_case.max(steps);
+        total += steps;
+    }
+    OpeningResult {
+        opening,
+        worst_case,
+        average: total as f64 / secrets.len() as f64,
+    }
+}
+
+// the results of every opening pattern, the best by worst case and the best by average
+pub fn search_openings() -> (Vec<OpeningResult>, OpeningResult, OpeningResult) {
+    let results: Vec<OpeningResult> = get_opening_patterns()
+        .into_iter()
+        .map(evaluate_opening)
+        .collect();
+    let mut best_worst_case = results[0];
+    let mut best_average = results[0];
+    for result in results.iter() {
+        if (result.worst_case, result.average)
+            < (best_worst_case.worst_case, best_worst_case.average)
+        {
+            best_worst_case = *result;
+        }
+        if (result.average, result.worst_case) < (best_average.average, best_average.worst_case) {
+            best_average = *result;
+        }
+    }
+    (results, best_worst_case, best_average)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::Colors;
+    use crate::opening::{
+        evaluate_opening, get_opening_patterns, get_pattern_string, parse_opening,
+    };
+    use crate::solver::Solver;
+    use crate::state_of_the_art_solver::solve;
+    use crate::strategy_tree::StrategyTree;
+
+    #[test]
+    fn parse_opening_accepts_codes_and_patterns() {
+        let expected = [Colors::Red, Colors::Red, Colors::Green, Colors::Green];
+        assert_eq!(Some(expected), parse_opening("0011"));
+        assert_eq!(Some(expected), parse_opening("AABB"));
+        assert_eq!(Some(expected), parse_opening("XXQQ"));
+        assert_eq!(
+            Some([Colors::Red, Colors::Green, Colors::Red, Colors::Blue]),
+            parse_opening("ABAC")
+        );
+        assert_eq!(
+            Some([Colors::Black, Colors::White, Colors::Yellow, Colors::Blue]),
+            parse_opening("5432")
+        );
+    }
+
+    #[test]
+    fn parse_opening_rejects_invalid_text() {
+        assert_eq!(None, parse_opening("AAB"));
+        assert_eq!(None, parse_opening("0016"));
+        assert_eq!(None, parse_opening("aabb"));
+        assert_eq!(None, parse_opening("AA11"));
+    }
+
+    #[test]
+    fn patterns_are_the_set_partitions() {
+        let patterns = get_opening_patterns();
+        assert_eq!(15, patterns.len());
+        assert_eq!("AAAA", get_pattern_string(&patterns[0]));
+        assert_eq!("ABCD", get_pattern_string(&patterns[14]));
+        for pattern in patterns.iter() {
+            let text = get_pattern_string(pattern);
+            assert_eq!(Some(*pattern), parse_opening(&text));
+        }
+    }
+
+    #[test]
+    fn evaluate_default_opening_matches_strategy_tree() {
+        let opening = [Colors::Red, Colors::Red, Colors::Green, Colors::Green];
+        let result = evaluate_opening(opening);
+        assert_eq!(opening, result.opening);
+        let depths = StrategyTree::build(&Solver::Builtin(solve))
+            .unwrap()
+            .get_depths();
+        assert_eq!(*depths.iter().max().unwrap(), result.worst_case);
+        let average = depths.iter().sum::<usize>() as f64 / depths.len() as f64;
+        assert!((average - result.average).abs() < 1e-9);
+    }
+}
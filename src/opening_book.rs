@@ -0,0 +1,205 @@
+use crate::evaluation::parse_evaluation;
+use crate::mastermind::{GuessStatus, Mastermind};
+use crate::mastermind_state::{get_string_from_guess, parse_guess, MastermindState, Values};
+use crate::solver::StrategyFn;
+use crate::strategy_tree::StrategyTree;
+use std::fmt::{Display, Error as FmtError, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Error, ErrorKind};
+
+// maps a history of guesses and their feedback to the next guess
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OpeningBook {
+    entries: Vec<(Vec<MastermindState>, Values)>,
+}
+
+// guess, feedback, guess, feedback, ..., next guess
+fn parse_line(line: &str) -> Option<(Vec<MastermindState>, Values)> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    if words.len().is_multiple_of(2) {
+        return None;
+    }
+    let mut history = Vec::new();
+    for pair in words.chunks_exact(2) {
+        history.push(MastermindState::new(
+            parse_guess(pair[0])?,
+            parse_evaluation(pair[1])?,
+        ));
+    }
+    Some((history, parse_guess(words[words.len() - 1])?))
+}
+
+impl OpeningBook {
+    // the first guesses of a strategy, depth 1 only holds the opening guess
+    pub fn from_tree(tree: &StrategyTree, depth: usize) -> OpeningBook {
+        let mut book = OpeningBook::default();
+        book.add_tree(tree, &mut Vec::new(), depth);
+        book
+    }
+
+    fn add_tree(&mut self, tree: &StrategyTree, history: &mut Vec<MastermindState>, depth: usize) {
+        if 0 == depth {
+            return;
+        }
+        self.entries.push((history.clone(), tree.get_guess()));
+        for (eval, child) in tree.get_children() {
+            history.push(MastermindState::new(tree.get_guess(), *eval));
+            self.add_tree(child, history, depth - 1);
+            history.pop();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn lookup(&self, history: &[MastermindState]) -> Option<Values> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| entry.as_slice() == history)
+            .map(|(_, guess)| *guess)
+    }
+}
+
+impl Display for OpeningBook {
+    fn fmt(&self, format: &mut Formatter) -> Result<(), FmtError> {
+        for (history, guess) in self.entries.iter() {
+            for state in history.iter() {
+                let eval = state.get_evaluation();
+                write!(
+                    format,
+                    "{} {},{} ",
+                    get_string_from_guess(&state.get_values()),
+                    eval.get_correct_match(),
+                    eval.get_color_present()
+                )?;
+            }
+            writeln!(format, "{}", get_string_from_guess(guess))?;
+        }
+        Ok(())
+    }
+}
+
+// one entry per line, empty lines and lines starting with '#' are skipped
+pub fn read_book<T: BufRead>(input: T) -> Result<OpeningBook, Error> {
+    let mut book = OpeningBook::default();
+    for (number, line) in input.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match parse_line(line) {
+            Some(entry) => book.entries.push(entry),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("invalid entry '{}' in line {}", line, number + 1),
+                ))
+            }
+        }
+    }
+    Ok(book)
+}
+
+pub fn load_book(path: &str) -> Result<OpeningBook, Error> {
+    read_book(BufReader::new(File::open(path)?))
+}
+
+// plays from the book as long as it knows the position, then asks the strategy
+pub fn solve_with_book(mm: &mut Mastermind, book: &OpeningBook, strategy: StrategyFn) -> Values {
+    loop {
+        let history = mm.get_guesses();
+        let colors = match book.lookup(&history).or_else(|| strategy(&history)) {
+            Some(colors) => colors,
+            // no code fits the feedback, the game ends with the last guess
+            None => return history[history.len() - 1].get_values(),
+        };
+        if let GuessStatus::Success = mm.guess(colors) {
+            return colors;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::Colors;
+    use crate::evaluation::Evaluation;
+    use crate::mastermind::Mastermind;
+    use crate::mastermind_state::{get_all_values, MastermindState};
+    use crate::opening_book::{load_book, read_book, solve_with_book, OpeningBook};
+    use crate::solver::Solver;
+    use crate::state_of_the_art_solver;
+    use crate::strategy_tree::StrategyTree;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn read_book_parses_histories() {
+        let input = "# opening\n0011\n\n0011 1,0 0234\n0011 1,0 0234 0,2 5512\n";
+        let book = read_book(input.as_bytes()).unwrap();
+        assert_eq!(3, book.len());
+        let opening = [Colors::Red, Colors::Red, Colors::Green, Colors::Green];
+        assert_eq!(Some(opening), book.lookup(&[]));
+        let history = [MastermindState::new(opening, Evaluation::new(1, 0))];
+        assert_eq!(
+            Some([Colors::Red, Colors::Blue, Colors::Yellow, Colors::White]),
+            book.lookup(&history)
+        );
+        let history = [MastermindState::new(opening, Evaluation::new(0, 1))];
+        assert_eq!(None, book.lookup(&history));
+    }
+
+    #[test]
+    fn read_book_reports_invalid_line() {
+        for line in ["0011 1,0", "0016", "0011 3,2 1234", "0011 1 1234"].iter() {
+            let input = format!("0011\n{}\n", line);
+            let error = read_book(input.as_bytes()).unwrap_err();
+            assert_eq!(ErrorKind::InvalidData, error.kind());
+            assert!(error.to_string().contains("line 2"));
+        }
+    }
+
+    #[test]
+    fn load_missing_book_fails() {
+        assert!(load_book("/nonexistent/book.txt").is_err());
+    }
+
+    #[test]
+    fn book_from_tree_survives_writing_and_reading() {
+        let tree = StrategyTree::build(&Solver::Builtin(state_of_the_art_solver::solve)).unwrap();
+        let book = OpeningBook::from_tree(&tree, 2);
+        assert_eq!(1 + tree.get_children().len(), book.len());
+        let text = book.to_string();
+        assert!(text.starts_with("0011\n"));
+        assert_eq!(book, read_book(text.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn solve_with_book_plays_like_the_solver() {
+        let tree = StrategyTree::build(&Solver::Builtin(state_of_the_art_solver::solve)).unwrap();
+        let book = OpeningBook::from_tree(&tree, 3);
+        for values in get_all_values().iter().step_by(97) {
+            let mut expected = Mastermind::new_with_state(*values);
+            expected.set_verbose(false);
+            state_of_the_art_solver::solve(&mut expected);
+
+            let mut mm = Mastermind::new_with_state(*values);
+            mm.set_verbose(false);
+            let solution = solve_with_book(&mut mm, &book, state_of_the_art_solver::next_guess);
+            assert!(mm.get_initial().are_values_equal(&solution));
+            assert_eq!(expected.get_guesses(), mm.get_guesses());
+        }
+    }
+
+    #[test]
+    fn solve_with_empty_book_falls_back_to_strategy() {
+        let values = [Colors::Black, Colors::Blue, Colors::Blue, Colors::Red];
+        let mut mm = Mastermind::new_with_state(values);
+        let solution = solve_with_book(
+            &mut mm,
+            &OpeningBook::default(),
+            state_of_the_art_solver::next_guess,
+        );
+        assert!(mm.get_initial().are_values_equal(&solution));
+    }
+}
@@ -0,0 +1,90 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+// number of items a thread takes from the shared queue at once
+const CHUNK_SIZE: usize = 16;
+
+pub fn num_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+// Index of the item with the smallest key. Idle threads keep taking the next
+// chunk until all items are scored, ties go to the lowest index so the result
+// is the same for every number of threads.
+pub fn min_by_key<T, K, F>(items: &[T], key: F) -> Option<usize>
+where
+    T: Sync,
+    K: Ord + Send,
+    F: Fn(&T) -> K + Sync,
+{
+    min_by_key_with_threads(items, key, num_threads())
+}
+
+pub fn min_by_key_with_threads<T, K, F>(items: &[T], key: F, num_threads: usize) -> Option<usize>
+where
+    T: Sync,
+    K: Ord + Send,
+    F: Fn(&T) -> K + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let worker = || {
+        let mut best: Option<(K, usize)> = None;
+        loop {
+            let start = next.fetch_add(CHUNK_SIZE, Ordering::Relaxed);
+            if start >= items.len() {
+                return best;
+            }
+            let end = items.len().min(start + CHUNK_SIZE);
+            for (i, item) in items[start..end].iter().enumerate() {
+                let candidate = (key(item), start + i);
+                if best.as_ref().is_none_or(|b| candidate < *b) {
+                    best = Some(candidate);
+                }
+            }
+        }
+    };
+
+    let num_threads = num_threads.clamp(1, items.len().div_ceil(CHUNK_SIZE).max(1));
+    let results: Vec<Option<(K, usize)>> = if 1 == num_threads {
+        vec![worker()]
+    } else {
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..num_threads).map(|_| scope.spawn(worker)).collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        })
+    };
+    results.into_iter().flatten().min().map(|(_, i)| i)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parallel::{min_by_key, min_by_key_with_threads};
+
+    #[test]
+    fn min_by_key_of_empty_slice_is_none() {
+        let items: [u32; 0] = [];
+        assert_eq!(None, min_by_key(&items, |x| *x));
+    }
+
+    #[test]
+    fn min_by_key_finds_minimum() {
+        let items: Vec<u32> = (0..1000).map(|x| (x * 7919) % 1009).collect();
+        let expected = items.iter().enumerate().min_by_key(|(_, x)| **x).unwrap().0;
+        assert_eq!(Some(expected), min_by_key(&items, |x| *x));
+    }
+
+    #[test]
+    fn ties_go_to_lowest_index_for_every_number_of_threads() {
+        let items: Vec<u32> = (0..1000).map(|x| x % 10).collect();
+        for num_threads in 1..9 {
+            assert_eq!(
+                Some(0),
+                min_by_key_with_threads(&items, |x| *x, num_threads)
+            );
+            assert_eq!(
+                Some(3),
+                min_by_key_with_threads(&items, |x| (*x + 7) % 10, num_threads)
+            );
+        }
+    }
+}
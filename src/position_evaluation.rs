@@ -0,0 +1,40 @@
+use crate::mastermind_state::NUM_ELEMENTS;
+use crate::util::{BLACK, CHAR, RESET};
+use std::fmt::{Display, Error, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionEvaluation {
+    correct: [bool; NUM_ELEMENTS],
+}
+
+impl PositionEvaluation {
+    pub fn new(correct: [bool; NUM_ELEMENTS]) -> Self {
+        PositionEvaluation { correct }
+    }
+}
+
+impl Display for PositionEvaluation {
+    fn fmt(&self, format: &mut Formatter) -> Result<(), Error> {
+        for correct in self.correct.iter() {
+            if *correct {
+                write!(format, "{}{}", BLACK, CHAR)?;
+            } else {
+                write!(format, "{}_", RESET)?;
+            }
+        }
+        write!(format, "{}", RESET)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::position_evaluation::PositionEvaluation;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            "\u{1b}[30m▉\u{1b}[0m_\u{1b}[0m_\u{1b}[30m▉\u{1b}[0m",
+            format!("{}", PositionEvaluation::new([true, false, false, true]))
+        );
+    }
+}
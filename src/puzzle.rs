@@ -0,0 +1,271 @@
+use crate::candidate_set::CandidateSet;
+use crate::code::{get_num_codes, pack, unpack, Code};
+use crate::mastermind_state::{get_string_from_guess, MastermindState, Values};
+use crate::scoring_rule::{Classic, ScoringRule};
+use crate::util::SeededRandom;
+use std::fmt::{Display, Error, Formatter};
+
+// random positions tried before the generator gives up
+const MAX_ATTEMPTS: usize = 500;
+
+// the reasoning depth tries every subset of the rows, so there cannot be many
+pub const MAX_ROWS: usize = 16;
+
+// guesses with feedback which leave exactly one code
+#[derive(Debug, Clone, PartialEq)]
+pub struct Puzzle {
+    secret: Values,
+    rows: Vec<MastermindState>,
+}
+
+impl Puzzle {
+    pub fn get_secret(&self) -> Values {
+        self.secret
+    }
+
+    pub fn get_rows(&self) -> &[MastermindState] {
+        &self.rows
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, format: &mut Formatter) -> Result<(), Error> {
+        for row in self.rows.iter() {
+            writeln!(format, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+fn filter(candidates: &mut CandidateSet, row: &MastermindState) {
+    let guess = pack(&row.get_values());
+    let eval = row.get_evaluation();
+    candidates.retain(|candidate| Classic.score_code(candidate, guess) == eval);
+}
+
+// codes which give every guess its feedback
+pub fn get_candidates(rows: &[MastermindState]) -> CandidateSet {
+    let mut candidates = CandidateSet::new_full();
+    for row in rows {
+        filter(&mut candidates, row);
+    }
+    candidates
+}
+
+// the only code consistent with the rows, None for no or several codes
+pub fn get_unique_solution(rows: &[MastermindState]) -> Option<Values> {
+    let candidates = get_candidates(rows);
+    match candidates.len() {
+        1 => candidates.iter().next().map(unpack),
+        _ => None,
+    }
+}
+
+// fewest rows which already single out the solution, the others are only distractions
+pub fn get_reasoning_depth(rows: &[MastermindState]) -> Result<Option<usize>, String> {
+    if MAX_ROWS < rows.len() {
+        return Err(format!(
+            "{} rows given, at most {} can be checked",
+            rows.len(),
+            MAX_ROWS
+        ));
+    }
+    if get_unique_solution(rows).is_none() {
+        return Ok(None);
+    }
+    Ok((1..=rows.len()).find(|&size| {
+        (0u32..1 << rows.len())
+            .filter(|subset| size == subset.count_ones() as usize)
+            .any(|subset| {
+                let chosen: Vec<MastermindState> = (0..rows.len())
+                    .filter(|row| 0 != subset & (1 << row))
+                    .map(|row| rows[row])
+                    .collect();
+                get_unique_solution(&chosen).is_some()
+            })
+    }))
+}
+
+// acceptable guesses compared before one is picked for a row
+const CHOICES: usize = 32;
+
+// every row but the last keeps several candidates, the last leaves only the secret
+fn try_generate(random: &mut SeededRandom, rows: usize) -> Option<Puzzle> {
+    let secret = random.next_usize(get_num_codes()) as Code;
+    let mut candidates = CandidateSet::new_full();
+    let mut puzzle = Puzzle {
+        secret: unpack(secret),
+        rows: Vec::new(),
+    };
+    for row in 0..rows {
+        let last = row + 1 == rows;
+        // the candidates shrink evenly, so a puzzle with many rows does not end early
+        let target = (get_num_codes() as f64).powf((rows - row - 1) as f64 / rows as f64);
+        let start = random.next_usize(get_num_codes());
+        let choices: Vec<(MastermindState, CandidateSet)> = (0..get_num_codes())
+            .map(|offset| ((start + offset) % get_num_codes()) as Code)
+            .filter(|guess| *guess != secret)
+            .map(|guess| {
+                let state = MastermindState::new(unpack(guess), Classic.score_code(secret, guess));
+                let mut remaining = candidates.clone();
+                filter(&mut remaining, &state);
+                (state, remaining)
+            })
+            .filter(|(_, remaining)| {
+                let size = remaining.len();
+                if last {
+                    1 == size
+                } else {
+                    1 < size && size < candidates.len()
+                }
+            })
+            .take(CHOICES)
+            .collect();
+        let distance = |remaining: &CandidateSet| (remaining.len() as f64 / target).ln().abs();
+        let (state, remaining) = choices
+            .into_iter()
+            .min_by(|(_, a), (_, b)| distance(a).total_cmp(&distance(b)))?;
+        puzzle.rows.push(state);
+        candidates = remaining;
+    }
+    Some(puzzle)
+}
+
+// more rows give more to read, a higher depth needs more of them combined
+pub fn generate_puzzle(seed: u64, rows: usize, min_depth: usize) -> Result<Puzzle, String> {
+    if 0 == rows || MAX_ROWS < rows || min_depth > rows {
+        return Err(format!(
+            "no puzzle with {} rows and depth {} possible",
+            rows, min_depth
+        ));
+    }
+    let mut random = SeededRandom::new(seed);
+    for _ in 0..MAX_ATTEMPTS {
+        if let Some(puzzle) = try_generate(&mut random, rows) {
+            if matches!(get_reasoning_depth(&puzzle.rows), Ok(Some(depth)) if min_depth <= depth) {
+                return Ok(puzzle);
+            }
+        }
+    }
+    Err(format!(
+        "no puzzle with {} rows and depth {} found in {} attempts",
+        rows, min_depth, MAX_ATTEMPTS
+    ))
+}
+
+// the answer to a puzzle as shown to the player
+pub fn show_solution(puzzle: &Puzzle, answer: &Values) -> String {
+    if *answer == puzzle.secret {
+        String::from("correct")
+    } else {
+        format!(
+            "wrong, the code was {}",
+            get_string_from_guess(&puzzle.secret)
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::Colors;
+    use crate::evaluation::Evaluation;
+    use crate::mastermind_state::MastermindState;
+    use crate::puzzle::{
+        generate_puzzle, get_candidates, get_reasoning_depth, get_unique_solution, show_solution,
+        MAX_ROWS,
+    };
+
+    fn row(values: [Colors; 4], correct_match: u8, color_present: u8) -> MastermindState {
+        MastermindState::new(values, Evaluation::new(correct_match, color_present))
+    }
+
+    #[test]
+    fn unique_solution() {
+        use Colors::*;
+        let rows = [row([Red, Red, Green, Green], 0, 0)];
+        assert_eq!(256, get_candidates(&rows).len());
+        assert_eq!(None, get_unique_solution(&rows));
+        // only the colors of the first guess are left and their order is known
+        let rows = [
+            row([Red, Green, Blue, Yellow], 0, 4),
+            row([Green, Red, Yellow, Blue], 0, 4),
+            row([Yellow, Blue, Red, Green], 0, 4),
+        ];
+        assert_eq!(Some([Blue, Yellow, Green, Red]), get_unique_solution(&rows));
+        // contradicting rows leave nothing
+        let rows = [row([Red; 4], 1, 0), row([Red; 4], 2, 0)];
+        assert_eq!(None, get_unique_solution(&rows));
+        assert_eq!(Ok(None), get_reasoning_depth(&rows));
+    }
+
+    #[test]
+    fn reasoning_depth_ignores_distractions() {
+        use Colors::*;
+        let rows = [
+            row([White, White, White, White], 0, 0),
+            row([Red, Green, Blue, Yellow], 0, 4),
+            row([Green, Red, Yellow, Blue], 0, 4),
+            row([Yellow, Blue, Red, Green], 0, 4),
+        ];
+        assert_eq!(Ok(Some(3)), get_reasoning_depth(&rows));
+    }
+
+    #[test]
+    fn generated_puzzles_have_one_solution() {
+        let puzzle = generate_puzzle(7, 5, 4).unwrap();
+        assert_eq!(5, puzzle.get_rows().len());
+        assert_eq!(
+            Some(puzzle.get_secret()),
+            get_unique_solution(puzzle.get_rows())
+        );
+        assert!(4 <= get_reasoning_depth(puzzle.get_rows()).unwrap().unwrap());
+        assert!(puzzle
+            .get_rows()
+            .iter()
+            .all(|row| !row.are_values_equal(&puzzle.get_secret())));
+        assert_eq!(puzzle, generate_puzzle(7, 5, 4).unwrap());
+        assert_ne!(puzzle, generate_puzzle(8, 5, 4).unwrap());
+    }
+
+    #[test]
+    fn impossible_puzzles_are_rejected() {
+        assert!(generate_puzzle(1, 0, 0).is_err());
+        assert!(generate_puzzle(1, 3, 4).is_err());
+        assert!(generate_puzzle(1, MAX_ROWS + 1, 1).is_err());
+    }
+
+    #[test]
+    fn too_many_rows_are_not_searched() {
+        use Colors::*;
+        // a unique solution repeated often enough to overflow a subset mask
+        let rows: Vec<MastermindState> = [
+            row([Red, Green, Blue, Yellow], 0, 4),
+            row([Green, Red, Yellow, Blue], 0, 4),
+            row([Yellow, Blue, Red, Green], 0, 4),
+        ]
+        .iter()
+        .cycle()
+        .take(33)
+        .copied()
+        .collect();
+        assert!(get_unique_solution(&rows).is_some());
+        assert_eq!(
+            Err(String::from("33 rows given, at most 16 can be checked")),
+            get_reasoning_depth(&rows)
+        );
+        assert!(get_reasoning_depth(&rows[..MAX_ROWS]).is_ok());
+    }
+
+    #[test]
+    fn answer_is_checked() {
+        let puzzle = generate_puzzle(3, 4, 1).unwrap();
+        assert_eq!("correct", show_solution(&puzzle, &puzzle.get_secret()));
+        let mut wrong = puzzle.get_secret();
+        wrong[0] = if Colors::Red == wrong[0] {
+            Colors::Green
+        } else {
+            Colors::Red
+        };
+        assert!(show_solution(&puzzle, &wrong).starts_with("wrong, the code was "));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::code::{get_num_codes, pack_evaluation, unpack, unpack_evaluation, Code};
+use crate::evaluation::Evaluation;
+use crate::mastermind_state::MastermindState;
+use std::hint::black_box;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+// bigger boards would need too much memory
+const MAX_CODES: usize = 4096;
+
+// evaluation of every guess against every secret
+pub struct ScoreTable {
+    num_codes: usize,
+    scores: Vec<u8>,
+}
+
+impl ScoreTable {
+    fn new() -> ScoreTable {
+        let num_codes = get_num_codes();
+        let mut scores = vec![0; num_codes * num_codes];
+        for secret in 0..num_codes {
+            let state = MastermindState::new_initial(unpack(secret as Code));
+            for guess in 0..num_codes {
+                scores[guess * num_codes + secret] =
+                    pack_evaluation(&state.diff(&unpack(guess as Code)));
+            }
+        }
+        ScoreTable { num_codes, scores }
+    }
+
+    // packed evaluation, see code::pack_evaluation
+    pub fn get(&self, secret: Code, guess: Code) -> u8 {
+        self.scores[guess as usize * self.num_codes + secret as usize]
+    }
+
+    pub fn evaluation(&self, secret: Code, guess: Code) -> Evaluation {
+        unpack_evaluation(self.get(secret, guess))
+    }
+}
+
+// built on first use, None if the board is too big
+pub fn get_score_table() -> Option<&'static ScoreTable> {
+    static SCORE_TABLE: OnceLock<ScoreTable> = OnceLock::new();
+    if get_num_codes() > MAX_CODES {
+        return None;
+    }
+    Some(SCORE_TABLE.get_or_init(ScoreTable::new))
+}
+
+// time to score every guess against every secret with diff and with the table
+pub fn benchmark() -> (Duration, Duration, Duration) {
+    let num_codes = get_num_codes() as Code;
+
+    let start = Instant::now();
+    let mut sum = 0u64;
+    for secret in 0..num_codes {
+        let state = MastermindState::new_initial(unpack(secret));
+        for guess in 0..num_codes {
+            sum += u64::from(pack_evaluation(&state.diff(&unpack(guess))));
+        }
+    }
+    black_box(sum);
+    let diff_time = start.elapsed();
+
+    let start = Instant::now();
+    let table = get_score_table().unwrap();
+    let build_time = start.elapsed();
+
+    let start = Instant::now();
+    let mut sum = 0u64;
+    for secret in 0..num_codes {
+        for guess in 0..num_codes {
+            sum += u64::from(table.get(black_box(secret), guess));
+        }
+    }
+    black_box(sum);
+    (diff_time, build_time, start.elapsed())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::code::{get_num_codes, Code};
+    use crate::mastermind_state::{get_all_values, MastermindState};
+    use crate::score_table::get_score_table;
+
+    #[test]
+    fn table_matches_diff() {
+        let table = get_score_table().unwrap();
+        let all_values = get_all_values();
+        for secret in (0..get_num_codes()).step_by(7) {
+            let state = MastermindState::new_initial(all_values[secret]);
+            for (guess, values) in all_values.iter().enumerate() {
+                assert_eq!(
+                    state.diff(values),
+                    table.evaluation(secret as Code, guess as Code)
+                );
+            }
+        }
+    }
+}
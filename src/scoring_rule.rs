@@ -0,0 +1,283 @@
+use crate::code::{unpack, Code};
+use crate::evaluation::Evaluation;
+use crate::mastermind_state::{MastermindState, Values, NUM_ELEMENTS};
+use crate::position_evaluation::PositionEvaluation;
+use crate::score_table::get_score_table;
+use crate::wordle_evaluation::{Marker, WordleEvaluation};
+use std::fmt::{Debug, Display};
+
+// decides which feedback the codemaker gives for a guess
+pub trait ScoringRule: Sync {
+    type Feedback: Copy + Debug + Display + PartialEq;
+
+    fn score(&self, secret: &Values, guess: &Values) -> Self::Feedback;
+
+    // rules which can be derived from black and white pegs look this up in the score table
+    fn score_code(&self, secret: Code, guess: Code) -> Self::Feedback {
+        self.score(&unpack(secret), &unpack(guess))
+    }
+
+    // true if moving positions or renaming colors of secret and guess alike keeps the feedback
+    fn is_symmetric(&self) -> bool {
+        false
+    }
+
+    // black and white pegs, which explanations need, only the classic rule gives both
+    fn get_classic_feedback(&self, _feedback: &Self::Feedback) -> Option<Evaluation> {
+        None
+    }
+}
+
+// black and white pegs
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Classic;
+
+impl ScoringRule for Classic {
+    type Feedback = Evaluation;
+
+    fn is_symmetric(&self) -> bool {
+        true
+    }
+
+    fn score(&self, secret: &Values, guess: &Values) -> Evaluation {
+        MastermindState::new_initial(*secret).diff(guess)
+    }
+
+    fn get_classic_feedback(&self, feedback: &Evaluation) -> Option<Evaluation> {
+        Some(*feedback)
+    }
+
+    fn score_code(&self, secret: Code, guess: Code) -> Evaluation {
+        match get_score_table() {
+            Some(table) => table.evaluation(secret, guess),
+            None => self.score(&unpack(secret), &unpack(guess)),
+        }
+    }
+}
+
+fn black_only(eval: Evaluation) -> Evaluation {
+    Evaluation::new(eval.get_correct_match(), 0)
+}
+
+// only black pegs, colors at the wrong position are not reported
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BlackOnly;
+
+impl ScoringRule for BlackOnly {
+    type Feedback = Evaluation;
+
+    fn is_symmetric(&self) -> bool {
+        true
+    }
+
+    fn score(&self, secret: &Values, guess: &Values) -> Evaluation {
+        black_only(Classic.score(secret, guess))
+    }
+
+    fn score_code(&self, secret: Code, guess: Code) -> Evaluation {
+        black_only(Classic.score_code(secret, guess))
+    }
+}
+
+fn colors_present(eval: Evaluation) -> Evaluation {
+    Evaluation::new(0, eval.get_correct_match() + eval.get_color_present())
+}
+
+// number of colors present, positions are ignored
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ColorsPresent;
+
+impl ScoringRule for ColorsPresent {
+    type Feedback = Evaluation;
+
+    fn is_symmetric(&self) -> bool {
+        true
+    }
+
+    fn score(&self, secret: &Values, guess: &Values) -> Evaluation {
+        colors_present(Classic.score(secret, guess))
+    }
+
+    fn score_code(&self, secret: Code, guess: Code) -> Evaluation {
+        colors_present(Classic.score_code(secret, guess))
+    }
+}
+
+// tells which positions are correct
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Positional;
+
+impl ScoringRule for Positional {
+    type Feedback = PositionEvaluation;
+
+    fn score(&self, secret: &Values, guess: &Values) -> PositionEvaluation {
+        let mut correct = [false; NUM_ELEMENTS];
+        for (i, c) in correct.iter_mut().enumerate() {
+            *c = secret[i] == guess[i];
+        }
+        PositionEvaluation::new(correct)
+    }
+}
+
+// a marker per position, duplicate colors are handled like in MastermindState::diff
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Wordle;
+
+impl ScoringRule for Wordle {
+    type Feedback = WordleEvaluation;
+
+    fn score(&self, secret: &Values, guess: &Values) -> WordleEvaluation {
+        let mut markers = [Marker::Absent; NUM_ELEMENTS];
+        let mut used_slots_secret = [false; NUM_ELEMENTS];
+
+        // correct matches need to be done first
+        for i in 0..guess.len() {
+            if guess[i] == secret[i] {
+                markers[i] = Marker::Correct;
+                used_slots_secret[i] = true;
+            }
+        }
+
+        for i in 0..guess.len() {
+            if Marker::Correct == markers[i] {
+                continue;
+            }
+            for (j, val) in secret.iter().enumerate() {
+                if guess[i] == *val && !used_slots_secret[j] {
+                    markers[i] = Marker::Present;
+                    used_slots_secret[j] = true;
+                    break;
+                }
+            }
+        }
+
+        WordleEvaluation::new(markers)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::code::pack;
+    use crate::colors::Colors;
+    use crate::evaluation::Evaluation;
+    use crate::position_evaluation::PositionEvaluation;
+    use crate::scoring_rule::{BlackOnly, Classic, ColorsPresent, Positional, ScoringRule, Wordle};
+    use crate::wordle_evaluation::{Marker, WordleEvaluation};
+
+    const SECRET: [Colors; 4] = [Colors::Black, Colors::Black, Colors::Green, Colors::Red];
+    const GUESS: [Colors; 4] = [Colors::Black, Colors::Red, Colors::Black, Colors::Blue];
+
+    #[test]
+    fn classic_matches_diff() {
+        assert_eq!(Evaluation::new(1, 2), Classic.score(&SECRET, &GUESS));
+    }
+
+    #[test]
+    fn score_code_matches_score() {
+        let (secret, guess) = (pack(&SECRET), pack(&GUESS));
+        assert_eq!(
+            Classic.score(&SECRET, &GUESS),
+            Classic.score_code(secret, guess)
+        );
+        assert_eq!(
+            BlackOnly.score(&SECRET, &GUESS),
+            BlackOnly.score_code(secret, guess)
+        );
+        assert_eq!(
+            ColorsPresent.score(&SECRET, &GUESS),
+            ColorsPresent.score_code(secret, guess)
+        );
+        assert_eq!(
+            Wordle.score(&SECRET, &GUESS),
+            Wordle.score_code(secret, guess)
+        );
+    }
+
+    #[test]
+    fn black_only_drops_color_present() {
+        assert_eq!(Evaluation::new(1, 0), BlackOnly.score(&SECRET, &GUESS));
+    }
+
+    #[test]
+    fn colors_present_ignores_position() {
+        assert_eq!(Evaluation::new(0, 3), ColorsPresent.score(&SECRET, &GUESS));
+        assert_eq!(Evaluation::new(0, 4), ColorsPresent.score(&SECRET, &SECRET));
+    }
+
+    #[test]
+    fn positional_reports_correct_positions() {
+        assert_eq!(
+            PositionEvaluation::new([true, false, false, false]),
+            Positional.score(&SECRET, &GUESS)
+        );
+        assert_eq!(
+            PositionEvaluation::new([true; 4]),
+            Positional.score(&SECRET, &SECRET)
+        );
+    }
+
+    #[test]
+    fn wordle_marks_every_position() {
+        assert_eq!(
+            WordleEvaluation::new([
+                Marker::Correct,
+                Marker::Present,
+                Marker::Present,
+                Marker::Absent
+            ]),
+            Wordle.score(&SECRET, &GUESS)
+        );
+        assert_eq!(
+            WordleEvaluation::new([Marker::Correct; 4]),
+            Wordle.score(&SECRET, &SECRET)
+        );
+    }
+
+    #[test]
+    fn wordle_marks_surplus_duplicates_absent() {
+        let secret = [Colors::Black, Colors::Black, Colors::Black, Colors::Blue];
+        let guess = [
+            Colors::Yellow,
+            Colors::Yellow,
+            Colors::Yellow,
+            Colors::Black,
+        ];
+        assert_eq!(
+            WordleEvaluation::new([
+                Marker::Absent,
+                Marker::Absent,
+                Marker::Absent,
+                Marker::Present
+            ]),
+            Wordle.score(&secret, &guess)
+        );
+
+        let secret = [Colors::Black, Colors::Green, Colors::Green, Colors::Red];
+        let guess = [Colors::Yellow, Colors::Black, Colors::Black, Colors::Black];
+        assert_eq!(
+            WordleEvaluation::new([
+                Marker::Absent,
+                Marker::Present,
+                Marker::Absent,
+                Marker::Absent
+            ]),
+            Wordle.score(&secret, &guess)
+        );
+    }
+
+    #[test]
+    fn wordle_agrees_with_classic_counts() {
+        for guess in [SECRET, GUESS, [Colors::Red; 4]].iter() {
+            let markers = format!("{:?}", Wordle.score(&SECRET, guess));
+            let eval = Classic.score(&SECRET, guess);
+            assert_eq!(
+                eval.get_correct_match() as usize,
+                markers.matches("Correct").count()
+            );
+            assert_eq!(
+                eval.get_color_present() as usize,
+                markers.matches("Present").count()
+            );
+        }
+    }
+}
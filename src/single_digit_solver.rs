@@ -7,7 +7,7 @@ pub fn solve(mm: &mut Mastermind) -> Values {
     let mut guess: Values = [Colors::Red; NUM_ELEMENTS];
     let mut eval;
     match mm.guess(guess) {
-        GuessStatus::Success => return guess,
+        GuessStatus::Success | GuessStatus::Rejected => return guess,
         GuessStatus::Incorrect(e) => eval = e,
     }
 
@@ -16,7 +16,7 @@ pub fn solve(mm: &mut Mastermind) -> Values {
         'colors_loop: for c in Colors::iter().skip(1) {
             current_guess[i] = *c;
             match mm.guess(current_guess) {
-                GuessStatus::Success => {
+                GuessStatus::Success | GuessStatus::Rejected => {
                     guess = current_guess;
                     break 'guess_loop;
                 }
@@ -1,26 +1,109 @@
+use crate::bot;
+use crate::knuth_solver;
 use crate::manual_solver;
 use crate::mastermind::Mastermind;
-use crate::mastermind_state::Values;
+use crate::mastermind_state::{MastermindState, Values};
 use crate::multi_digit_solver;
+use crate::scoring_rule::{Classic, ScoringRule};
 use crate::single_digit_solver;
 use crate::state_of_the_art_solver;
+use crate::static_solver;
+use std::io::Error;
 
-pub type SolverFn = fn(&mut Mastermind) -> Values;
+pub type SolverFn<R = Classic> = fn(&mut Mastermind<R>) -> Values;
 
-pub fn parse_args(args: Vec<String>) -> SolverFn {
+// picks the next guess only from the guesses made so far, None when no code fits the feedback
+pub type StrategyFn = fn(&[MastermindState]) -> Option<Values>;
+
+// the solver and whether a human plays it
+pub fn parse_args(args: Vec<String>) -> (SolverFn, bool) {
     let solver: SolverFn;
+    let mut interactive = false;
     if 2 > args.len() {
         solver = manual_solver::solve;
+        interactive = true;
     } else if "single" == args[1] {
         solver = single_digit_solver::solve;
     } else if "multi" == args[1] {
         solver = multi_digit_solver::solve;
     } else if "state" == args[1] {
         solver = state_of_the_art_solver::solve;
+    } else if "knuth" == args[1] {
+        solver = knuth_solver::solve;
+    } else if "static" == args[1] {
+        solver = static_solver::solve;
     } else {
         solver = manual_solver::solve;
+        interactive = true;
+    }
+    (solver, interactive)
+}
+
+// only the manual and the candidate filtering solvers work with every scoring rule
+pub fn parse_rule_args<R: ScoringRule>(args: &[String]) -> (SolverFn<R>, bool) {
+    if 2 > args.len() {
+        (manual_solver::solve_with_rule, true)
+    } else if "state" == args[1] {
+        (state_of_the_art_solver::solve_with_rule, false)
+    } else if "knuth" == args[1] {
+        (knuth_solver::solve_with_rule, false)
+    } else {
+        (manual_solver::solve_with_rule, true)
+    }
+}
+
+// solvers which always play the same guesses for the same feedback
+pub fn get_deterministic_solver(name: &str) -> Option<SolverFn> {
+    match name {
+        "single" => Some(single_digit_solver::solve),
+        "multi" => Some(multi_digit_solver::solve),
+        "state" => Some(state_of_the_art_solver::solve),
+        "knuth" => Some(knuth_solver::solve),
+        "static" => Some(static_solver::solve),
+        _ => None,
+    }
+}
+
+// a solver which plays whole games for the tournament, the tree and the hot seat
+#[derive(Debug, Clone)]
+pub enum Solver {
+    Builtin(SolverFn),
+    // an external solver speaking the bot protocol, started for every game
+    Bot(Vec<String>),
+}
+
+impl Solver {
+    // a bot which dies or breaks the protocol is an error, not a lost game
+    pub fn solve(&self, mm: &mut Mastermind) -> Result<Values, Error> {
+        match self {
+            Solver::Builtin(solver) => Ok(solver(mm)),
+            Solver::Bot(command) => bot::solve_with_bot(mm, command),
+        }
+    }
+}
+
+// a deterministic solver by name or a bot as "bot:<command> [args...]"
+pub fn get_solver(name: &str) -> Option<Solver> {
+    match name.strip_prefix("bot:") {
+        Some(command) => {
+            let command: Vec<String> = command.split_whitespace().map(String::from).collect();
+            if command.is_empty() {
+                None
+            } else {
+                Some(Solver::Bot(command))
+            }
+        }
+        None => get_deterministic_solver(name).map(Solver::Builtin),
+    }
+}
+
+// solvers which can continue a game from any history
+pub fn get_strategy(name: &str) -> Option<StrategyFn> {
+    match name {
+        "state" => Some(state_of_the_art_solver::next_guess),
+        "knuth" => Some(knuth_solver::next_guess),
+        _ => None,
     }
-    solver
 }
 
 #[cfg(test)]
@@ -39,9 +122,16 @@ pub mod test_utils {
 mod test {
     use crate::colors::Colors;
     use crate::mastermind::Mastermind;
+    use crate::scoring_rule::BlackOnly;
     use crate::solver::test_utils::check_solution;
-    use crate::solver::{parse_args, SolverFn};
-    use crate::{manual_solver, multi_digit_solver, single_digit_solver, state_of_the_art_solver};
+    use crate::solver::{
+        get_deterministic_solver, get_solver, get_strategy, parse_args, parse_rule_args, Solver,
+        SolverFn, StrategyFn,
+    };
+    use crate::{
+        knuth_solver, manual_solver, multi_digit_solver, single_digit_solver,
+        state_of_the_art_solver, static_solver,
+    };
     use std::string::String;
 
     #[test]
@@ -50,7 +140,7 @@ mod test {
     }
 
     fn parse_args_tests(args: Vec<String>, func: SolverFn) {
-        assert!(parse_args(args) as *const SolverFn == func as *const SolverFn);
+        assert!(parse_args(args).0 as *const SolverFn == func as *const SolverFn);
     }
 
     #[test]
@@ -82,6 +172,22 @@ mod test {
         );
     }
 
+    #[test]
+    fn knuth_results_in_knuth_solver() {
+        parse_args_tests(
+            vec![String::from("bla"), String::from("knuth")],
+            knuth_solver::solve,
+        );
+    }
+
+    #[test]
+    fn static_results_in_static_solver() {
+        parse_args_tests(
+            vec![String::from("bla"), String::from("static")],
+            static_solver::solve,
+        );
+    }
+
     #[test]
     fn unknown_string_results_in_manual_solver() {
         parse_args_tests(
@@ -90,6 +196,66 @@ mod test {
         );
     }
 
+    #[test]
+    fn only_the_manual_solver_is_interactive() {
+        let args = |name: &str| vec![String::from("bla"), String::from(name)];
+        assert!(parse_args(vec![String::from("bla")]).1);
+        assert!(parse_args(args("fdjafda")).1);
+        assert!(!parse_args(args("single")).1);
+        assert!(parse_rule_args::<BlackOnly>(&args("single")).1);
+        assert!(!parse_rule_args::<BlackOnly>(&args("knuth")).1);
+    }
+
+    #[test]
+    fn state_with_rule_results_in_state_of_the_art_solver() {
+        let args = vec![String::from("bla"), String::from("state")];
+        let (solver, _): (SolverFn<BlackOnly>, bool) = parse_rule_args(&args);
+        let expected: SolverFn<BlackOnly> = state_of_the_art_solver::solve_with_rule;
+        assert!(solver as *const SolverFn<BlackOnly> == expected as *const SolverFn<BlackOnly>);
+    }
+
+    #[test]
+    fn other_solver_with_rule_results_in_manual_solver() {
+        let args = vec![String::from("bla"), String::from("single")];
+        let (solver, _): (SolverFn<BlackOnly>, bool) = parse_rule_args(&args);
+        let expected: SolverFn<BlackOnly> = manual_solver::solve_with_rule;
+        assert!(solver as *const SolverFn<BlackOnly> == expected as *const SolverFn<BlackOnly>);
+    }
+
+    #[test]
+    fn manual_solver_is_not_deterministic() {
+        let solver = get_deterministic_solver("knuth").unwrap();
+        assert!(solver as *const SolverFn == knuth_solver::solve as *const SolverFn);
+        assert!(get_deterministic_solver("manual").is_none());
+    }
+
+    #[test]
+    fn bots_are_solvers() {
+        match get_solver("bot:./mybot --fast") {
+            Some(Solver::Bot(command)) => assert_eq!(vec!["./mybot", "--fast"], command),
+            other => panic!("expected a bot, got {:?}", other),
+        }
+        assert!(matches!(get_solver("knuth"), Some(Solver::Builtin(_))));
+        assert!(get_solver("bot:").is_none());
+        assert!(get_solver("manual").is_none());
+    }
+
+    #[test]
+    fn dead_bot_is_an_error() {
+        let mut mm = Mastermind::new_with_state([Colors::Red; 4]);
+        let bot = Solver::Bot(vec![String::from("/nonexistent/bot")]);
+        assert!(bot.solve(&mut mm).is_err());
+        assert!(mm.get_guesses().is_empty());
+    }
+
+    #[test]
+    fn only_filtering_solvers_have_a_strategy() {
+        let strategy = get_strategy("state").unwrap();
+        let expected: StrategyFn = state_of_the_art_solver::next_guess;
+        assert!(strategy as *const StrategyFn == expected as *const StrategyFn);
+        assert!(get_strategy("single").is_none());
+    }
+
     macro_rules! solver_tests {($solvers:expr; $($name:ident: $value:expr,)*) => {
         $(
             #[test]
@@ -110,7 +276,9 @@ mod test {
         [
             ("single_digit_solver", single_digit_solver::solve as SolverFn),
             ("multi_digit_solver", multi_digit_solver::solve as SolverFn),
-            ("state_of_the_art_solver", state_of_the_art_solver::solve as SolverFn)];
+            ("state_of_the_art_solver", state_of_the_art_solver::solve as SolverFn),
+            ("knuth_solver", knuth_solver::solve as SolverFn),
+            ("static_solver", static_solver::solve as SolverFn)];
         solve_with_red_state_solves_the_game: [Colors::Red; 4],
         solve_with_green_state_solves_the_game: [Colors::Green; 4],
         solve_with_white_state_solves_the_game: [Colors::White; 4],
@@ -1,54 +1,107 @@
+use crate::candidate_set::CandidateSet;
+use crate::code::{pack, unpack, Code};
 use crate::colors::Colors;
-use crate::evaluation::Evaluation;
 use crate::mastermind::{GuessStatus, Mastermind};
-use crate::mastermind_state::{MastermindState, Values};
+use crate::mastermind_state::{get_all_values, MastermindState, Values};
+use crate::scoring_rule::{Classic, ScoringRule};
 
-struct AllStates {
-    states: Vec<Values>,
+pub struct AllStates {
+    states: CandidateSet,
 }
 
 impl AllStates {
-    fn new() -> AllStates {
-        let mut states = Vec::new();
-        for c0 in Colors::iter() {
-            for c1 in Colors::iter() {
-                for c2 in Colors::iter() {
-                    for c3 in Colors::iter() {
-                        states.push([*c0, *c1, *c2, *c3]);
-                    }
-                }
-            }
+    pub fn new(states: Vec<Values>) -> AllStates {
+        let codes: Vec<_> = states.iter().map(pack).collect();
+        AllStates {
+            states: CandidateSet::from_codes(&codes),
         }
-        AllStates { states }
     }
 
-    fn reduce(&mut self, values: &Values, eval: &Evaluation) -> Values {
-        let state = MastermindState::new_initial(*values);
-        self.states
-            .retain(|possible_state| state.diff(possible_state) == *eval);
+    // keeps the codes which fit the feedback, returns the others except the guess
+    pub fn filter<R: ScoringRule>(
+        &mut self,
+        rule: &R,
+        values: &Values,
+        eval: &R::Feedback,
+    ) -> CandidateSet {
+        let guess = pack(values);
+        let before = self.states.clone();
+        // the guess itself has to go, some rules cannot tell it apart from the solution
+        self.states.retain(|possible_state| {
+            possible_state != guess && rule.score_code(possible_state, guess) == *eval
+        });
+        let mut rejected = before.difference(&self.states);
+        rejected.remove(guess);
+        rejected
+    }
+
+    // None when no code fits the feedback
+    fn reduce<R: ScoringRule>(
+        &mut self,
+        rule: &R,
+        values: &Values,
+        eval: &R::Feedback,
+    ) -> Option<Values> {
+        self.filter(rule, values, eval);
         self.new_pick()
     }
 
-    fn new_pick(&self) -> Values {
-        self.states[self.states.len() / 2]
+    pub fn insert(&mut self, code: Code) {
+        self.states.insert(code);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Code> + '_ {
+        self.states.iter()
+    }
+
+    pub fn new_pick(&self) -> Option<Values> {
+        self.states.iter().nth(self.states.len() / 2).map(unpack)
     }
 }
 
 pub fn solve(mm: &mut Mastermind) -> Values {
-    let mut states = AllStates::new();
-    let mut colors: Values = [Colors::Red, Colors::Red, Colors::Green, Colors::Green];
+    solve_with_rule(mm)
+}
+
+pub fn solve_with_rule<R: ScoringRule>(mm: &mut Mastermind<R>) -> Values {
+    solve_with_opening(mm, [Colors::Red, Colors::Red, Colors::Green, Colors::Green])
+}
+
+// the opening is replaced when it is not an allowed guess
+pub fn solve_with_opening<R: ScoringRule>(mm: &mut Mastermind<R>, opening: Values) -> Values {
+    let mut states = AllStates::new(mm.get_allowed_guesses());
+    let mut colors = opening;
+    if !mm.is_allowed(&colors) {
+        colors = states.new_pick().unwrap();
+    }
     while let GuessStatus::Incorrect(e) = mm.guess(colors) {
-        colors = states.reduce(&colors, &e);
+        match states.reduce(mm.get_rule(), &colors, &e) {
+            Some(next) => colors = next,
+            None => break,
+        }
     }
     colors
 }
 
+// the guess solve would play after this history
+pub fn next_guess(history: &[MastermindState]) -> Option<Values> {
+    let mut states = AllStates::new(get_all_values());
+    let mut colors: Values = [Colors::Red, Colors::Red, Colors::Green, Colors::Green];
+    for state in history {
+        colors = states.reduce(&Classic, &state.get_values(), &state.get_evaluation())?;
+    }
+    Some(colors)
+}
+
 #[cfg(test)]
 mod test {
+    use crate::colors::Colors;
+    use crate::mastermind::Mastermind;
+    use crate::mastermind_state::Values;
+    use crate::scoring_rule::{BlackOnly, ColorsPresent, Positional, ScoringRule, Wordle};
     use crate::solver::test_utils::check_solution;
     use crate::solver::SolverFn;
-    use crate::state_of_the_art_solver::solve;
-    use crate::Mastermind;
+    use crate::state_of_the_art_solver::{next_guess, solve, solve_with_rule};
 
     #[test]
     fn solve_has_correct_type() {
@@ -61,4 +114,66 @@ mod test {
         let solution = solve(&mut mm);
         check_solution(&mm.get_initial().get_values(), &mm, &solution);
     }
+
+    fn solve_all_with_rule<R: ScoringRule + Copy>(rule: R) {
+        let secrets: [Values; 3] = [
+            [Colors::Black; 4],
+            [Colors::Red, Colors::Black, Colors::Black, Colors::Red],
+            [Colors::White, Colors::Blue, Colors::Yellow, Colors::Black],
+        ];
+        for values in secrets.iter() {
+            let mut mm = Mastermind::new_with_state_and_rule(*values, rule);
+            let solution = solve_with_rule(&mut mm);
+            assert!(mm.get_initial().are_values_equal(&solution));
+        }
+    }
+
+    #[test]
+    fn solve_with_black_only_rule() {
+        solve_all_with_rule(BlackOnly);
+    }
+
+    #[test]
+    fn solve_with_colors_present_rule() {
+        solve_all_with_rule(ColorsPresent);
+    }
+
+    #[test]
+    fn solve_with_positional_rule() {
+        solve_all_with_rule(Positional);
+    }
+
+    #[test]
+    fn solve_with_dictionary() {
+        let dictionary: Vec<Values> = vec![
+            [Colors::Black, Colors::Red, Colors::Blue, Colors::White],
+            [Colors::Green, Colors::White, Colors::Red, Colors::Yellow],
+            [Colors::Blue, Colors::White, Colors::Red, Colors::Yellow],
+            [Colors::Yellow, Colors::Yellow, Colors::Red, Colors::Green],
+        ];
+        for _ in 0..10 {
+            let mut mm = Mastermind::new_with_dictionary(dictionary.clone());
+            let solution = solve(&mut mm);
+            assert!(mm.get_initial().are_values_equal(&solution));
+            for guess in mm.get_guesses().iter() {
+                assert!(dictionary.contains(&guess.get_values()));
+            }
+        }
+    }
+
+    #[test]
+    fn solve_with_wordle_rule() {
+        solve_all_with_rule(Wordle);
+    }
+
+    #[test]
+    fn next_guess_continues_the_game() {
+        let values = [Colors::Green, Colors::White, Colors::White, Colors::Blue];
+        let mut mm = Mastermind::new_with_state(values);
+        solve(&mut mm);
+        let guesses = mm.get_guesses();
+        for i in 0..guesses.len() {
+            assert_eq!(Some(guesses[i].get_values()), next_guess(&guesses[..i]));
+        }
+    }
 }
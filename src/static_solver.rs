@@ -0,0 +1,124 @@
+use crate::code::{get_num_codes, pack_evaluation, unpack, Code, NUM_EVALUATIONS};
+use crate::mastermind::{GuessStatus, Mastermind};
+use crate::mastermind_state::{get_all_values, MastermindState, Values};
+use crate::scoring_rule::{Classic, ScoringRule};
+use crate::symmetry::Symmetries;
+use std::sync::OnceLock;
+
+// Picks guesses one by one which split the codes into the most groups with
+// different feedback, until every code has its own combination of feedback.
+// This is greedy, so the set is small but not necessarily minimal.
+fn find_static_guesses() -> Vec<Values> {
+    let num_values = get_num_codes();
+    let score = |code: usize, guess: usize| {
+        pack_evaluation(&Classic.score_code(code as Code, guess as Code)) as usize
+    };
+
+    let mut guesses = Vec::new();
+    // codes with the same group got the same feedback for every guess so far
+    let mut groups = vec![0usize; num_values];
+    let mut num_groups = 1;
+    let mut seen = vec![false; num_values * NUM_EVALUATIONS];
+    let all_codes: Vec<Code> = (0..num_values as Code).collect();
+    while num_groups < num_values {
+        let mut best = (0, 0);
+        // guesses which are equivalent for the guesses so far split the codes alike
+        let representatives = Symmetries::new(&guesses).representatives(&all_codes);
+        for guess in representatives.iter().map(|guess| *guess as usize) {
+            seen.iter_mut().for_each(|s| *s = false);
+            let mut count = 0;
+            for (code, group) in groups.iter().enumerate() {
+                let key = group * NUM_EVALUATIONS + score(code, guess);
+                if !seen[key] {
+                    seen[key] = true;
+                    count += 1;
+                }
+            }
+            if count > best.0 {
+                best = (count, guess);
+            }
+        }
+
+        let mut renumber = vec![usize::MAX; num_values * NUM_EVALUATIONS];
+        num_groups = 0;
+        for (code, group) in groups.iter_mut().enumerate() {
+            let key = *group * NUM_EVALUATIONS + score(code, best.1);
+            if usize::MAX == renumber[key] {
+                renumber[key] = num_groups;
+                num_groups += 1;
+            }
+            *group = renumber[key];
+        }
+        guesses.push(unpack(best.1 as Code));
+    }
+    guesses
+}
+
+// the set only depends on the board, so it is searched once,
+// its size is an upper bound for the smallest distinguishing set
+pub fn get_static_guesses() -> &'static [Values] {
+    static STATIC_GUESSES: OnceLock<Vec<Values>> = OnceLock::new();
+    STATIC_GUESSES.get_or_init(find_static_guesses)
+}
+
+// commits to all guesses before looking at the feedback, then takes one final guess
+pub fn solve(mm: &mut Mastermind) -> Values {
+    let guesses = get_static_guesses();
+    let statuses: Vec<GuessStatus> = guesses.iter().map(|guess| mm.guess(*guess)).collect();
+
+    if let Some(i) = statuses.iter().position(|s| GuessStatus::Success == *s) {
+        return guesses[i];
+    }
+    let solution = *get_all_values()
+        .iter()
+        .find(|code| {
+            let state = MastermindState::new_initial(**code);
+            guesses
+                .iter()
+                .zip(statuses.iter())
+                .all(|(guess, status)| GuessStatus::Incorrect(state.diff(guess)) == *status)
+        })
+        .unwrap();
+    mm.guess(solution);
+    solution
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mastermind_state::{get_all_values, MastermindState};
+    use crate::solver::test_utils::check_solution;
+    use crate::solver::SolverFn;
+    use crate::static_solver::{get_static_guesses, solve};
+    use crate::Mastermind;
+    use std::collections::HashSet;
+
+    #[test]
+    fn solve_has_correct_type() {
+        let _solvefn: SolverFn = solve;
+    }
+
+    #[test]
+    fn solve_solves_the_game() {
+        let mut mm = Mastermind::new();
+        let solution = solve(&mut mm);
+        check_solution(&mm.get_initial().get_values(), &mm, &solution);
+        assert!(mm.get_guesses().len() <= get_static_guesses().len() + 1);
+    }
+
+    #[test]
+    fn static_guesses_distinguish_every_code() {
+        let guesses = get_static_guesses();
+        assert!(guesses.len() <= 8);
+        let signatures: HashSet<Vec<_>> = get_all_values()
+            .iter()
+            .map(|code| {
+                let state = MastermindState::new_initial(*code);
+                guesses
+                    .iter()
+                    .map(|guess| format!("{:?}", state.diff(guess)))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(get_all_values().len(), signatures.len());
+    }
+}
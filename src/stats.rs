@@ -0,0 +1,475 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write as FmtWrite;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Error, ErrorKind, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// rows of the guess distribution, longer games share one row
+pub const MAX_ROWS: usize = 10;
+
+pub const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// one finished interactive game
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameRecord {
+    pub player: String,
+    // e.g. "4x6-classic"
+    pub board: String,
+    pub guesses: usize,
+    pub won: bool,
+    pub duration: Duration,
+    // seconds since 1970
+    pub finished: u64,
+    // guesses taken back in a practice game
+    pub takebacks: usize,
+}
+
+impl GameRecord {
+    // a solved game
+    pub fn new(player: &str, board: &str, guesses: usize, duration: Duration) -> Self {
+        GameRecord {
+            player: String::from(player),
+            board: String::from(board),
+            guesses,
+            won: true,
+            duration,
+            finished: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_secs()),
+            takebacks: 0,
+        }
+    }
+
+    // e.g. the time ran out, however many guesses were made
+    pub fn new_lost(player: &str, board: &str, guesses: usize, duration: Duration) -> Self {
+        GameRecord {
+            won: false,
+            ..GameRecord::new(player, board, guesses, duration)
+        }
+    }
+
+    // games with takebacks are practice and not part of any ranking
+    pub fn is_ranked(&self) -> bool {
+        0 == self.takebacks
+    }
+}
+
+// $MASTERMIND_PLAYER, the login name or "player"
+pub fn get_player_name() -> String {
+    env::var("MASTERMIND_PLAYER")
+        .or_else(|_| env::var("USER"))
+        .unwrap_or_else(|_| String::from("player"))
+}
+
+// $MASTERMIND_STATS or ~/.mastermind_stats
+pub fn get_stats_path() -> PathBuf {
+    if let Ok(path) = env::var("MASTERMIND_STATS") {
+        return PathBuf::from(path);
+    }
+    let home = env::var("HOME").unwrap_or_else(|_| String::from("."));
+    PathBuf::from(home).join(".mastermind_stats")
+}
+
+// tab separated: player, board, guesses, won, milliseconds, finished, takebacks
+fn format_record(record: &GameRecord) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+        record.player,
+        record.board,
+        record.guesses,
+        u8::from(record.won),
+        record.duration.as_millis(),
+        record.finished,
+        record.takebacks
+    )
+}
+
+fn parse_record(line: &str) -> Option<GameRecord> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    // records written before takebacks existed have six fields
+    if !(6..=7).contains(&fields.len()) || fields[0].is_empty() {
+        return None;
+    }
+    Some(GameRecord {
+        player: String::from(fields[0]),
+        board: String::from(fields[1]),
+        guesses: fields[2].parse().ok()?,
+        won: "1" == fields[3],
+        duration: Duration::from_millis(fields[4].parse().ok()?),
+        finished: fields[5].parse().ok()?,
+        takebacks: match fields.get(6) {
+            Some(takebacks) => takebacks.parse().ok()?,
+            None => 0,
+        },
+    })
+}
+
+pub fn append_record(path: &PathBuf, record: &GameRecord) -> Result<(), Error> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", format_record(record))
+}
+
+pub fn read_records<T: BufRead>(input: T) -> Result<Vec<GameRecord>, Error> {
+    let mut records = Vec::new();
+    for (number, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse_record(&line) {
+            Some(record) => records.push(record),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("invalid game record in line {}", number + 1),
+                ))
+            }
+        }
+    }
+    Ok(records)
+}
+
+// e.g. the start of a daily game by its result, the file is rewritten
+pub fn replace_record(path: &PathBuf, old: &GameRecord, new: &GameRecord) -> Result<(), Error> {
+    let mut records = load_records(path)?;
+    match records.iter().rposition(|record| record == old) {
+        Some(index) => records[index] = new.clone(),
+        None => records.push(new.clone()),
+    }
+    let mut text = String::new();
+    for record in records.iter() {
+        writeln!(text, "{}", format_record(record)).unwrap();
+    }
+    let temporary = path.with_extension("tmp");
+    fs::write(&temporary, text)?;
+    fs::rename(&temporary, path)
+}
+
+// nobody has played yet when the file is missing
+pub fn load_records(path: &PathBuf) -> Result<Vec<GameRecord>, Error> {
+    match File::open(path) {
+        Ok(file) => read_records(BufReader::new(file)),
+        Err(error) if ErrorKind::NotFound == error.kind() => Ok(Vec::new()),
+        Err(error) => Err(error),
+    }
+}
+
+// true if the player finished a game on this board on this day
+pub fn has_completed(records: &[GameRecord], player: &str, board: &str, day: u64) -> bool {
+    records.iter().any(|record| {
+        record.player == player && record.board == board && record.finished / SECONDS_PER_DAY == day
+    })
+}
+
+// current and longest series of won games
+fn get_streaks(games: &[&GameRecord]) -> (usize, usize) {
+    let (mut current, mut longest) = (0, 0);
+    for game in games {
+        current = if game.won { current + 1 } else { 0 };
+        longest = longest.max(current);
+    }
+    (current, longest)
+}
+
+fn average_guesses(games: &[&GameRecord]) -> f64 {
+    games.iter().map(|game| game.guesses).sum::<usize>() as f64 / games.len() as f64
+}
+
+fn win_rate(games: &[&GameRecord]) -> f64 {
+    100.0 * games.iter().filter(|game| game.won).count() as f64 / games.len() as f64
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{}:{:02}.{}",
+        seconds / 60,
+        seconds % 60,
+        duration.subsec_millis() / 100
+    )
+}
+
+pub fn show_player_stats(records: &[GameRecord], player: &str) -> String {
+    let played: Vec<&GameRecord> = records.iter().filter(|r| r.player == player).collect();
+    let games: Vec<&GameRecord> = played.iter().copied().filter(|r| r.is_ranked()).collect();
+    let practice = played.len() - games.len();
+    if games.is_empty() {
+        return match practice {
+            0 => format!("{} has not played yet\n", player),
+            _ => format!("{} has only played {} practice games\n", player, practice),
+        };
+    }
+    let mut text = String::new();
+    let (current, longest) = get_streaks(&games);
+    writeln!(text, "{}", player).unwrap();
+    writeln!(text, "games played:   {}", games.len()).unwrap();
+    if 0 < practice {
+        writeln!(text, "practice games: {}", practice).unwrap();
+    }
+    writeln!(text, "win rate:       {:.1}%", win_rate(&games)).unwrap();
+    writeln!(text, "current streak: {}", current).unwrap();
+    writeln!(text, "longest streak: {}", longest).unwrap();
+
+    writeln!(text, "guess distribution:").unwrap();
+    let most = games.iter().map(|game| game.guesses).max().unwrap();
+    for guesses in 1..=most.min(MAX_ROWS) {
+        let count = games
+            .iter()
+            .filter(|game| game.won && game.guesses == guesses)
+            .count();
+        writeln!(text, "{:4} {:4} {}", guesses, count, "▉".repeat(count)).unwrap();
+    }
+    let more = games
+        .iter()
+        .filter(|game| game.won && MAX_ROWS < game.guesses)
+        .count();
+    if 0 < more {
+        writeln!(text, "more {:4} {}", more, "▉".repeat(more)).unwrap();
+    }
+    let lost = games.iter().filter(|game| !game.won).count();
+    if 0 < lost {
+        writeln!(text, "lost {:4} {}", lost, "▉".repeat(lost)).unwrap();
+    }
+
+    writeln!(text, "average guesses by board:").unwrap();
+    let mut boards: Vec<&str> = games.iter().map(|game| game.board.as_str()).collect();
+    boards.sort_unstable();
+    boards.dedup();
+    for board in boards {
+        let on_board: Vec<&GameRecord> = games
+            .iter()
+            .copied()
+            .filter(|game| game.board == board)
+            .collect();
+        writeln!(
+            text,
+            "  {:16} {:6.3} ({} games)",
+            board,
+            average_guesses(&on_board),
+            on_board.len()
+        )
+        .unwrap();
+    }
+
+    writeln!(text, "best times:").unwrap();
+    let mut wins: Vec<&GameRecord> = games.iter().copied().filter(|game| game.won).collect();
+    wins.sort_by_key(|game| game.duration);
+    for game in wins.iter().take(3) {
+        writeln!(
+            text,
+            "  {} on {} with {} guesses",
+            format_duration(game.duration),
+            game.board,
+            game.guesses
+        )
+        .unwrap();
+    }
+    text
+}
+
+// players ranked by win rate and then by average guesses, optionally on one board
+pub fn show_leaderboard(records: &[GameRecord], board: Option<&str>) -> String {
+    let mut players: HashMap<&str, Vec<&GameRecord>> = HashMap::new();
+    for record in records.iter().filter(|record| record.is_ranked()) {
+        if board.is_none_or(|board| board == record.board) {
+            players.entry(&record.player).or_default().push(record);
+        }
+    }
+    if players.is_empty() {
+        return String::from("no games played yet\n");
+    }
+    let mut ranking: Vec<(&str, Vec<&GameRecord>)> = players.into_iter().collect();
+    ranking.sort_by(|(a_name, a), (b_name, b)| {
+        win_rate(b)
+            .total_cmp(&win_rate(a))
+            .then(average_guesses(a).total_cmp(&average_guesses(b)))
+            .then(a_name.cmp(b_name))
+    });
+    let mut text = String::new();
+    writeln!(
+        text,
+        "rank  player            games  win rate  average  best time"
+    )
+    .unwrap();
+    for (rank, (player, games)) in ranking.iter().enumerate() {
+        let best = games
+            .iter()
+            .filter(|game| game.won)
+            .map(|game| game.duration)
+            .min();
+        writeln!(
+            text,
+            "{:4}  {:16}  {:5}  {:7.1}%  {:7.3}  {}",
+            rank + 1,
+            player,
+            games.len(),
+            win_rate(games),
+            average_guesses(games),
+            best.map_or(String::from("-"), format_duration)
+        )
+        .unwrap();
+    }
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stats::{
+        append_record, get_streaks, has_completed, load_records, read_records, replace_record,
+        show_leaderboard, show_player_stats, GameRecord, MAX_ROWS,
+    };
+    use std::env;
+    use std::fs;
+    use std::io::ErrorKind;
+    use std::time::Duration;
+
+    fn record(player: &str, board: &str, guesses: usize, seconds: u64) -> GameRecord {
+        let mut record = GameRecord::new(player, board, guesses, Duration::from_secs(seconds));
+        record.finished = 1_700_000_000;
+        record
+    }
+
+    fn lost_record(player: &str, board: &str, guesses: usize, seconds: u64) -> GameRecord {
+        GameRecord {
+            won: false,
+            ..record(player, board, guesses, seconds)
+        }
+    }
+
+    fn records() -> Vec<GameRecord> {
+        vec![
+            record("ann", "4x6-classic", 5, 90),
+            lost_record("bob", "4x6-classic", 12, 300),
+            record("ann", "4x6-wordle", 4, 61),
+            record("bob", "4x6-classic", 6, 200),
+            lost_record("ann", "4x6-classic", 11, 400),
+            record("ann", "4x6-classic", 5, 75),
+        ]
+    }
+
+    #[test]
+    fn solved_games_are_won_however_long() {
+        assert!(record("ann", "b", MAX_ROWS + 1, 1).won);
+        assert!(!GameRecord::new_lost("ann", "b", 3, Duration::from_secs(1)).won);
+    }
+
+    #[test]
+    fn records_survive_writing_and_reading() {
+        let path = env::temp_dir().join(format!("mastermind_stats_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        assert!(load_records(&path).unwrap().is_empty());
+        for record in records().iter() {
+            append_record(&path, record).unwrap();
+        }
+        assert_eq!(records(), load_records(&path).unwrap());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn started_game_is_replaced_by_its_result() {
+        let path = env::temp_dir().join(format!("mastermind_replace_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let started = GameRecord::new_lost("ann", "4x6-daily", 0, Duration::ZERO);
+        append_record(&path, &records()[0]).unwrap();
+        append_record(&path, &started).unwrap();
+        let finished = record("ann", "4x6-daily", 5, 90);
+        replace_record(&path, &started, &finished).unwrap();
+        assert_eq!(
+            vec![records()[0].clone(), finished.clone()],
+            load_records(&path).unwrap()
+        );
+        // a missing record is added
+        replace_record(&path, &started, &records()[1]).unwrap();
+        assert_eq!(3, load_records(&path).unwrap().len());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn read_records_without_takebacks() {
+        let records = read_records("ann\tb\t4\t1\t100\t0\nann\tb\t4\t1\t100\t0\t2\n".as_bytes());
+        let records = records.unwrap();
+        assert_eq!(0, records[0].takebacks);
+        assert_eq!(2, records[1].takebacks);
+    }
+
+    #[test]
+    fn read_records_reports_invalid_line() {
+        let error =
+            read_records("ann\tb\t4\t1\t100\t0\nann\tb\tx\t1\t100\t0\n".as_bytes()).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, error.kind());
+        assert!(error.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn completed_on_the_day_of_the_record() {
+        let records = records();
+        let day = records[0].finished / 86_400;
+        assert!(has_completed(&records, "ann", "4x6-wordle", day));
+        assert!(!has_completed(&records, "bob", "4x6-wordle", day));
+        assert!(!has_completed(&records, "ann", "4x6-wordle", day + 1));
+    }
+
+    #[test]
+    fn streaks_are_counted_in_order() {
+        let records = records();
+        let ann: Vec<&GameRecord> = records.iter().filter(|r| "ann" == r.player).collect();
+        assert_eq!((1, 2), get_streaks(&ann));
+        assert_eq!((0, 0), get_streaks(&[]));
+    }
+
+    #[test]
+    fn player_stats() {
+        let text = show_player_stats(&records(), "ann");
+        assert!(text.contains("games played:   4\n"));
+        assert!(text.contains("win rate:       75.0%\n"));
+        assert!(text.contains("longest streak: 2\n"));
+        assert!(text.contains("   5    2 ▉▉\n"));
+        assert!(text.contains("  10    0 \nlost    1 ▉\n"));
+        assert!(text.contains("  4x6-classic       7.000 (3 games)\n"));
+        assert!(text.contains("best times:\n  1:01.0 on 4x6-wordle with 4 guesses\n  1:15.0"));
+        assert_eq!(
+            "eve has not played yet\n",
+            show_player_stats(&records(), "eve")
+        );
+        // a long game is still won
+        let mut records = records();
+        records.push(record("ann", "4x6-classic", MAX_ROWS + 3, 500));
+        let text = show_player_stats(&records, "ann");
+        assert!(text.contains("  10    0 \nmore    1 ▉\nlost    1 ▉\n"));
+    }
+
+    #[test]
+    fn takebacks_are_not_ranked() {
+        let mut records = records();
+        let mut practice = record("eve", "4x6-classic", 2, 10);
+        practice.takebacks = 3;
+        records.push(practice.clone());
+        assert_eq!(
+            "eve has only played 1 practice games\n",
+            show_player_stats(&records, "eve")
+        );
+        assert!(!show_leaderboard(&records, None).contains("eve"));
+
+        practice.player = String::from("ann");
+        records.push(practice);
+        let text = show_player_stats(&records, "ann");
+        assert!(text.contains("games played:   4\npractice games: 1\n"));
+        assert!(text.contains("  4x6-classic       7.000 (3 games)\n"));
+    }
+
+    #[test]
+    fn leaderboard_ranks_by_win_rate() {
+        let text = show_leaderboard(&records(), None);
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[1].contains("ann") && lines[1].contains("75.0%"));
+        assert!(lines[2].contains("bob") && lines[2].contains("3:20.0"));
+        let text = show_leaderboard(&records(), Some("4x6-wordle"));
+        assert_eq!(2, text.lines().count());
+        assert_eq!(
+            "no games played yet\n",
+            show_leaderboard(&records(), Some("5x8-classic"))
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use crate::evaluation::Evaluation;
+use crate::mastermind::Mastermind;
+use crate::mastermind_state::{
+    get_all_values, get_string_from_guess, MastermindState, Values, NUM_ELEMENTS,
+};
+use crate::solver::Solver;
+use std::fmt::Write;
+
+// a guess and the next guess of the solver for every feedback it can get
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyTree {
+    guess: Values,
+    // the guess was the secret in one of the games
+    solved: bool,
+    // the guess was the last one of a game
+    last: bool,
+    children: Vec<(Evaluation, StrategyTree)>,
+}
+
+impl StrategyTree {
+    fn new(guess: Values) -> Self {
+        StrategyTree {
+            guess,
+            solved: false,
+            last: false,
+            children: Vec::new(),
+        }
+    }
+
+    // plays the solver against every secret, which only works for deterministic solvers
+    pub fn build(solver: &Solver) -> Result<StrategyTree, String> {
+        let mut root: Option<StrategyTree> = None;
+        for secret in get_all_values() {
+            let mut mm = Mastermind::new_with_state(secret);
+            mm.set_verbose(false);
+            solver
+                .solve(&mut mm)
+                .map_err(|error| format!("solver failed: {}", error))?;
+            let guesses = mm.get_guesses();
+            let node = root.get_or_insert_with(|| StrategyTree::new(guesses[0].get_values()));
+            node.insert(&guesses, false)?;
+        }
+        Ok(root.unwrap())
+    }
+
+    // a static solver plays its whole batch even after the secret, so the game
+    // goes on after a full match until the last guess
+    fn insert(&mut self, guesses: &[MastermindState], found: bool) -> Result<(), String> {
+        let state = guesses[0];
+        if !state.are_values_equal(&self.guess) {
+            return Err(format!(
+                "solver guessed {} instead of {}, it is not deterministic",
+                get_string_from_guess(&state.get_values()),
+                get_string_from_guess(&self.guess)
+            ));
+        }
+        let eval = state.get_evaluation();
+        let found = found || NUM_ELEMENTS as u8 == eval.get_correct_match();
+        self.solved |= NUM_ELEMENTS as u8 == eval.get_correct_match();
+        if 1 == guesses.len() {
+            if !found {
+                return Err(format!(
+                    "solver stopped after {} without finding the secret",
+                    get_string_from_guess(&state.get_values())
+                ));
+            }
+            self.last = true;
+            return Ok(());
+        }
+        let index = match self.children.iter().position(|(e, _)| *e == eval) {
+            Some(index) => index,
+            None => {
+                let child = StrategyTree::new(guesses[1].get_values());
+                self.children.push((eval, child));
+                self.children.len() - 1
+            }
+        };
+        self.children[index].1.insert(&guesses[1..], found)
+    }
+
+    pub fn get_guess(&self) -> Values {
+        self.guess
+    }
+
+    pub fn get_children(&self) -> &[(Evaluation, StrategyTree)] {
+        &self.children
+    }
+
+    // number of guesses played for every secret
+    pub fn get_depths(&self) -> Vec<usize> {
+        let mut depths = Vec::new();
+        self.add_depths(1, &mut depths);
+        depths
+    }
+
+    fn add_depths(&self, depth: usize, depths: &mut Vec<usize>) {
+        if self.last {
+            depths.push(depth);
+        }
+        for (_, child) in self.children.iter() {
+            child.add_depths(depth + 1, depths);
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph strategy {\n");
+        let mut next_id = 0;
+        self.write_dot(&mut dot, &mut next_id);
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn write_dot(&self, dot: &mut String, next_id: &mut usize) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+        let shape = if self.solved {
+            "doublecircle"
+        } else {
+            "circle"
+        };
+        writeln!(
+            dot,
+            "  n{} [label=\"{}\", shape={}];",
+            id,
+            get_string_from_guess(&self.guess),
+            shape
+        )
+        .unwrap();
+        for (eval, child) in self.children.iter() {
+            let child_id = child.write_dot(dot, next_id);
+            writeln!(
+                dot,
+                "  n{} -> n{} [label=\"{},{}\"];",
+                id,
+                child_id,
+                eval.get_correct_match(),
+                eval.get_color_present()
+            )
+            .unwrap();
+        }
+        id
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.write_json(&mut json);
+        json
+    }
+
+    fn write_json(&self, json: &mut String) {
+        write!(
+            json,
+            "{{\"guess\":\"{}\",\"solved\":{},\"children\":[",
+            get_string_from_guess(&self.guess),
+            self.solved
+        )
+        .unwrap();
+        for (i, (eval, child)) in self.children.iter().enumerate() {
+            if 0 < i {
+                json.push(',');
+            }
+            write!(
+                json,
+                "{{\"correct_match\":{},\"color_present\":{},\"next\":",
+                eval.get_correct_match(),
+                eval.get_color_present()
+            )
+            .unwrap();
+            child.write_json(json);
+            json.push('}');
+        }
+        json.push_str("]}");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::Colors;
+    use crate::evaluation::Evaluation;
+    use crate::mastermind::Mastermind;
+    use crate::mastermind_state::{get_all_values, Values};
+    use crate::single_digit_solver;
+    use crate::solver::Solver;
+    use crate::state_of_the_art_solver;
+    use crate::static_solver::{self, get_static_guesses};
+    use crate::strategy_tree::StrategyTree;
+
+    #[test]
+    fn tree_covers_every_secret() {
+        let tree = StrategyTree::build(&Solver::Builtin(state_of_the_art_solver::solve)).unwrap();
+        assert_eq!(
+            [Colors::Red, Colors::Red, Colors::Green, Colors::Green],
+            tree.guess
+        );
+        let depths = tree.get_depths();
+        assert_eq!(get_all_values().len(), depths.len());
+        assert!(depths.iter().all(|depth| *depth >= 1 && *depth <= 10));
+    }
+
+    #[test]
+    fn tree_follows_the_games() {
+        let tree = StrategyTree::build(&Solver::Builtin(single_digit_solver::solve)).unwrap();
+        let secret = [Colors::Green, Colors::Black, Colors::Red, Colors::White];
+        let mut mm = Mastermind::new_with_state(secret);
+        mm.set_verbose(false);
+        single_digit_solver::solve(&mut mm);
+
+        let mut node = &tree;
+        let guesses = mm.get_guesses();
+        for state in guesses.iter().take(guesses.len() - 1) {
+            assert_eq!(state.get_values(), node.guess);
+            let eval = state.get_evaluation();
+            node = &node.children.iter().find(|(e, _)| *e == eval).unwrap().1;
+        }
+        assert_eq!(secret, node.guess);
+        assert!(node.solved);
+    }
+
+    #[test]
+    fn static_tree_plays_the_whole_batch() {
+        let tree = StrategyTree::build(&Solver::Builtin(static_solver::solve)).unwrap();
+        let depths = tree.get_depths();
+        assert_eq!(get_all_values().len(), depths.len());
+        // every game plays the batch, the secrets outside of it take one more guess
+        let batch = get_static_guesses().len();
+        assert_eq!(batch + 1, *depths.iter().max().unwrap());
+        assert_eq!(batch, *depths.iter().min().unwrap());
+        assert_eq!(
+            batch,
+            depths.iter().filter(|depth| batch == **depth).count()
+        );
+
+        // the second guess of the batch is solved and the batch goes on after it
+        let secret = get_static_guesses()[1];
+        let mut mm = Mastermind::new_with_state(secret);
+        mm.set_verbose(false);
+        mm.guess(get_static_guesses()[0]);
+        let eval = mm.get_guesses()[0].get_evaluation();
+        let node = &tree.children.iter().find(|(e, _)| *e == eval).unwrap().1;
+        assert_eq!(secret, node.guess);
+        assert!(node.solved);
+        let solved = Evaluation::new(4, 0);
+        let next = &node.children.iter().find(|(e, _)| *e == solved).unwrap().1;
+        assert_eq!(get_static_guesses()[2], next.guess);
+    }
+
+    fn random_solver(mm: &mut Mastermind) -> Values {
+        let mut guess = mm.get_initial().get_values();
+        guess.rotate_left(1);
+        if mm.get_initial().are_values_equal(&guess) {
+            guess[0] = Colors::from(guess[0] as u8 + 1);
+        }
+        mm.guess(guess);
+        let solution = mm.get_initial().get_values();
+        mm.guess(solution);
+        solution
+    }
+
+    #[test]
+    fn non_deterministic_solver_is_rejected() {
+        assert!(StrategyTree::build(&Solver::Builtin(random_solver)).is_err());
+    }
+
+    #[test]
+    fn export_to_dot_and_json() {
+        let mut tree = StrategyTree::new([Colors::Red; 4]);
+        let mut child = StrategyTree::new([Colors::Green; 4]);
+        child.solved = true;
+        tree.children.push((Evaluation::new(0, 0), child));
+        assert_eq!(
+            "digraph strategy {\n  n0 [label=\"0000\", shape=circle];\n  n1 [label=\"1111\", shape=doublecircle];\n  n0 -> n1 [label=\"0,0\"];\n}\n",
+            tree.to_dot()
+        );
+        assert_eq!(
+            "{\"guess\":\"0000\",\"solved\":false,\"children\":[{\"correct_match\":0,\"color_present\":0,\"next\":{\"guess\":\"1111\",\"solved\":true,\"children\":[]}}]}",
+            tree.to_json()
+        );
+    }
+}
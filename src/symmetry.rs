@@ -0,0 +1,192 @@
+use crate::code::{pack, unpack, Code};
+use crate::colors::Colors;
+use crate::mastermind_state::{Values, NUM_ELEMENTS};
+
+type Permutation = [usize; NUM_ELEMENTS];
+
+fn get_permutations() -> Vec<Permutation> {
+    let mut permutations = Vec::new();
+    let mut current: Permutation = [0; NUM_ELEMENTS];
+    let mut used = [false; NUM_ELEMENTS];
+    add_permutations(0, &mut current, &mut used, &mut permutations);
+    permutations
+}
+
+fn add_permutations(
+    position: usize,
+    current: &mut Permutation,
+    used: &mut [bool; NUM_ELEMENTS],
+    permutations: &mut Vec<Permutation>,
+) {
+    if NUM_ELEMENTS == position {
+        permutations.push(*current);
+        return;
+    }
+    for i in 0..NUM_ELEMENTS {
+        if !used[i] {
+            used[i] = true;
+            current[position] = i;
+            add_permutations(position + 1, current, used, permutations);
+            used[i] = false;
+        }
+    }
+}
+
+fn get_color_permutations(colors: &[Colors]) -> Vec<Vec<Colors>> {
+    if colors.is_empty() {
+        return vec![Vec::new()];
+    }
+    let mut permutations = Vec::new();
+    for (i, first) in colors.iter().enumerate() {
+        let mut rest = colors.to_vec();
+        rest.remove(i);
+        for mut permutation in get_color_permutations(&rest) {
+            permutation.insert(0, *first);
+            permutations.push(permutation);
+        }
+    }
+    permutations
+}
+
+// Permutations of positions and colors which map every guess so far onto itself.
+// Such a permutation maps any guess onto one with the same outcome, so only one
+// guess of each equivalence class has to be scored. Colors which were never played
+// can be exchanged freely.
+pub struct Symmetries {
+    // position permutation and the image of every color
+    symmetries: Vec<(Permutation, Vec<Colors>)>,
+    free_colors: Vec<Colors>,
+}
+
+impl Symmetries {
+    pub fn new(history: &[Values]) -> Symmetries {
+        let used_colors: Vec<Colors> = Colors::iter()
+            .filter(|c| history.iter().any(|guess| guess.contains(c)))
+            .cloned()
+            .collect();
+        let free_colors: Vec<Colors> = Colors::iter()
+            .filter(|c| !used_colors.contains(c))
+            .cloned()
+            .collect();
+
+        let mut symmetries = Vec::new();
+        for images in get_color_permutations(&used_colors) {
+            let mut color_map: Vec<Colors> = Colors::iter().cloned().collect();
+            for (color, image) in used_colors.iter().zip(images.iter()) {
+                color_map[*color as usize] = *image;
+            }
+            for permutation in get_permutations() {
+                let fixes_history = history.iter().all(|guess| {
+                    (0..NUM_ELEMENTS).all(|i| color_map[guess[permutation[i]] as usize] == guess[i])
+                });
+                if fixes_history {
+                    symmetries.push((permutation, color_map.clone()));
+                }
+            }
+        }
+        Symmetries {
+            symmetries,
+            free_colors,
+        }
+    }
+
+    // renames free colors in the order they appear, this gives the smallest code
+    fn rename_free_colors(&self, values: &mut Values) {
+        let mut renamed: Vec<(Colors, Colors)> = Vec::new();
+        for val in values.iter_mut() {
+            if !self.free_colors.contains(val) {
+                continue;
+            }
+            match renamed.iter().find(|(from, _)| from == val) {
+                Some((_, to)) => *val = *to,
+                None => {
+                    let to = self.free_colors[renamed.len()];
+                    renamed.push((*val, to));
+                    *val = to;
+                }
+            }
+        }
+    }
+
+    // smallest code of the equivalence class
+    pub fn canonical(&self, code: Code) -> Code {
+        let values = unpack(code);
+        let mut best = code;
+        for (permutation, color_map) in self.symmetries.iter() {
+            let mut mapped: Values = values;
+            for (i, val) in mapped.iter_mut().enumerate() {
+                *val = color_map[values[permutation[i]] as usize];
+            }
+            self.rename_free_colors(&mut mapped);
+            best = best.min(pack(&mapped));
+        }
+        best
+    }
+
+    // the guess with the smallest code of every equivalence class
+    pub fn representatives(&self, guesses: &[Code]) -> Vec<Code> {
+        guesses
+            .iter()
+            .filter(|guess| self.canonical(**guess) == **guess)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::code::{get_num_codes, pack, Code};
+    use crate::colors::Colors;
+    use crate::symmetry::{get_permutations, Symmetries};
+
+    fn all_codes() -> Vec<Code> {
+        (0..get_num_codes() as Code).collect()
+    }
+
+    #[test]
+    fn permutations_of_positions() {
+        assert_eq!(24, get_permutations().len());
+    }
+
+    #[test]
+    fn opening_has_one_guess_per_pattern() {
+        let representatives = Symmetries::new(&[]).representatives(&all_codes());
+        // AAAA, AAAB, AABB, AABC and ABCD
+        assert_eq!(5, representatives.len());
+        assert!(representatives.contains(&pack(&[
+            Colors::Red,
+            Colors::Red,
+            Colors::Green,
+            Colors::Green
+        ])));
+    }
+
+    #[test]
+    fn played_colors_and_positions_are_distinguished() {
+        let guess = [Colors::Red, Colors::Red, Colors::Green, Colors::Green];
+        let symmetries = Symmetries::new(&[guess]);
+        // red and green can only be exchanged together with both halves
+        let halves = pack(&[Colors::Blue, Colors::Red, Colors::Green, Colors::Green]);
+        let swapped = pack(&[Colors::Red, Colors::Red, Colors::Blue, Colors::Green]);
+        assert_eq!(symmetries.canonical(halves), symmetries.canonical(swapped));
+        let colors_only = pack(&[Colors::Green, Colors::Green, Colors::Red, Colors::Red]);
+        assert_eq!(colors_only, symmetries.canonical(colors_only));
+        // unplayed colors are interchangeable
+        let blue = pack(&[Colors::Blue, Colors::Red, Colors::Red, Colors::Red]);
+        let black = pack(&[Colors::Black, Colors::Red, Colors::Red, Colors::Red]);
+        assert_eq!(symmetries.canonical(blue), symmetries.canonical(black));
+        let other_half = pack(&[Colors::Red, Colors::Red, Colors::Blue, Colors::Red]);
+        assert!(symmetries.canonical(blue) != symmetries.canonical(other_half));
+    }
+
+    #[test]
+    fn without_symmetries_every_guess_is_a_representative() {
+        let history = [
+            [Colors::Red, Colors::Green, Colors::Blue, Colors::Yellow],
+            [Colors::White, Colors::Black, Colors::Red, Colors::Green],
+            [Colors::Red, Colors::Red, Colors::Red, Colors::Green],
+        ];
+        let representatives = Symmetries::new(&history).representatives(&all_codes());
+        assert_eq!(get_num_codes(), representatives.len());
+    }
+}
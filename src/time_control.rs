@@ -0,0 +1,213 @@
+use crate::stats::format_duration;
+use std::io::{stdin, BufRead, Error, ErrorKind};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// the engine only asks the clock, so tests can decide how much time passes
+pub trait Clock {
+    // time since the clock was started
+    fn elapsed(&self) -> Duration;
+}
+
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+// limits for the whole game and for every single guess, None means no limit
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TimeControl {
+    pub game: Option<Duration>,
+    pub guess: Option<Duration>,
+}
+
+impl TimeControl {
+    pub fn is_limited(&self) -> bool {
+        self.game.is_some() || self.guess.is_some()
+    }
+
+    // time left for the current guess, None without any limit
+    pub fn get_remaining(&self, game_time: Duration, guess_time: Duration) -> Option<Duration> {
+        let game = self.game.map(|limit| limit.saturating_sub(game_time));
+        let guess = self.guess.map(|limit| limit.saturating_sub(guess_time));
+        match (game, guess) {
+            (Some(game), Some(guess)) => Some(game.min(guess)),
+            (game, guess) => game.or(guess),
+        }
+    }
+
+    // running out of time loses the game
+    pub fn is_over(&self, game_time: Duration, guess_time: Duration) -> bool {
+        self.get_remaining(game_time, guess_time)
+            .is_some_and(|left| left.is_zero())
+    }
+
+    pub fn show_clock(&self, game_time: Duration, guess_time: Duration) -> String {
+        let mut clock = Vec::new();
+        if let Some(limit) = self.game {
+            let left = limit.saturating_sub(game_time);
+            clock.push(format!("game {}", format_duration(left)));
+        }
+        if let Some(limit) = self.guess {
+            let left = limit.saturating_sub(guess_time);
+            clock.push(format!("guess {}", format_duration(left)));
+        }
+        format!("time left: {}", clock.join(", "))
+    }
+}
+
+// takes e.g. "game=300 guess=30" out of the arguments, limits in seconds
+pub fn parse_time_control(args: &[String]) -> Result<(TimeControl, Vec<String>), String> {
+    let mut control = TimeControl::default();
+    let mut rest = Vec::new();
+    for arg in args {
+        let (key, seconds) = match arg.split_once('=') {
+            Some((key @ ("game" | "guess"), seconds)) => (key, seconds),
+            _ => {
+                rest.push(arg.clone());
+                continue;
+            }
+        };
+        let seconds = seconds
+            .parse::<u64>()
+            .ok()
+            .filter(|seconds| 0 < *seconds)
+            .ok_or_else(|| format!("invalid number of seconds '{}'", seconds))?;
+        let limit = Some(Duration::from_secs(seconds));
+        match key {
+            "game" => control.game = limit,
+            _ => control.guess = limit,
+        }
+    }
+    Ok((control, rest))
+}
+
+// reads the lines in the background, so waiting for one can time out
+pub struct TimedInput {
+    lines: Receiver<String>,
+}
+
+impl TimedInput {
+    pub fn new() -> Self {
+        let (sender, lines) = channel();
+        thread::spawn(move || {
+            for line in stdin().lock().lines() {
+                let sent = line.map(|line| sender.send(line).is_ok());
+                if !sent.unwrap_or(false) {
+                    break;
+                }
+            }
+        });
+        TimedInput { lines }
+    }
+
+    // None if no line came in before the wait was over
+    pub fn get_line(&self, wait: Option<Duration>) -> Result<Option<String>, Error> {
+        let line = match wait {
+            Some(wait) => self.lines.recv_timeout(wait),
+            None => self
+                .lines
+                .recv()
+                .map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match line {
+            Ok(line) => Ok(Some(line)),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => {
+                Err(Error::new(ErrorKind::UnexpectedEof, "no more guesses"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::time_control::{parse_time_control, TimeControl};
+    use std::time::Duration;
+
+    fn seconds(seconds: u64) -> Option<Duration> {
+        Some(Duration::from_secs(seconds))
+    }
+
+    #[test]
+    fn remaining_time_is_the_smaller_limit() {
+        let control = TimeControl {
+            game: seconds(60),
+            guess: seconds(20),
+        };
+        let zero = Duration::ZERO;
+        assert_eq!(seconds(20), control.get_remaining(zero, zero));
+        assert_eq!(
+            seconds(10),
+            control.get_remaining(seconds(50).unwrap(), zero)
+        );
+        assert_eq!(
+            Some(zero),
+            control.get_remaining(zero, seconds(25).unwrap())
+        );
+        assert!(control.is_over(zero, seconds(25).unwrap()));
+        assert!(!control.is_over(zero, seconds(5).unwrap()));
+        let control = TimeControl::default();
+        assert_eq!(None, control.get_remaining(zero, zero));
+        assert!(!control.is_limited());
+        assert!(!control.is_over(seconds(1000).unwrap(), zero));
+    }
+
+    #[test]
+    fn clock_shows_the_time_left() {
+        let control = TimeControl {
+            game: seconds(300),
+            guess: seconds(30),
+        };
+        assert_eq!(
+            "time left: game 4:50.0, guess 0:20.0",
+            control.show_clock(seconds(10).unwrap(), seconds(10).unwrap())
+        );
+    }
+
+    #[test]
+    fn parse_limits() {
+        let args =
+            |text: &str| -> Vec<String> { text.split_whitespace().map(String::from).collect() };
+        assert_eq!(
+            Ok((
+                TimeControl {
+                    game: seconds(300),
+                    guess: seconds(30)
+                },
+                args("manual wordle")
+            )),
+            parse_time_control(&args("manual game=300 wordle guess=30"))
+        );
+        assert_eq!(
+            Ok((
+                TimeControl {
+                    game: None,
+                    guess: seconds(30)
+                },
+                Vec::new()
+            )),
+            parse_time_control(&args("guess=30"))
+        );
+        assert_eq!(
+            Ok((TimeControl::default(), args("seed=30"))),
+            parse_time_control(&args("seed=30"))
+        );
+        assert!(parse_time_control(&args("game=0")).is_err());
+        assert!(parse_time_control(&args("guess=soon")).is_err());
+    }
+}
@@ -0,0 +1,427 @@
+use crate::code::{get_num_codes, unpack, Code};
+use crate::mastermind::Mastermind;
+use crate::mastermind_state::{get_string_from_guess, parse_guess, Values};
+use crate::solver::Solver;
+use crate::util::SeededRandom;
+use std::fmt::{Display, Error as FmtError, Formatter, Write as FmtWrite};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Error, ErrorKind};
+
+// differences with a smaller p-value are reported as significant
+pub const SIGNIFICANCE: f64 = 0.05;
+
+const INITIAL_RATING: f64 = 1500.0;
+const RATING_FACTOR: f64 = 16.0;
+
+// the same seed gives the same secrets, a secret can be drawn twice
+pub fn pick_secrets(seed: u64, count: usize) -> Vec<Values> {
+    let mut random = SeededRandom::new(seed);
+    (0..count)
+        .map(|_| unpack(random.next_usize(get_num_codes()) as Code))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    name: String,
+    // number of guesses for every secret
+    guesses: Vec<usize>,
+}
+
+impl Entry {
+    pub fn average(&self) -> f64 {
+        self.guesses.iter().sum::<usize>() as f64 / self.guesses.len() as f64
+    }
+
+    pub fn worst_case(&self) -> usize {
+        self.guesses.iter().copied().max().unwrap_or(0)
+    }
+
+    // share of the secrets found within the limit
+    pub fn win_rate(&self, limit: usize) -> f64 {
+        let wins = self
+            .guesses
+            .iter()
+            .filter(|guesses| **guesses <= limit)
+            .count();
+        wins as f64 / self.guesses.len() as f64
+    }
+}
+
+// paired comparison over the same secrets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignTest {
+    // secrets where the first solver needed fewer guesses
+    pub better: usize,
+    pub worse: usize,
+    // two-sided, ties are left out
+    pub p_value: f64,
+}
+
+pub fn sign_test(first: &[usize], second: &[usize]) -> SignTest {
+    let better = first.iter().zip(second).filter(|(a, b)| a < b).count();
+    let worse = first.iter().zip(second).filter(|(a, b)| a > b).count();
+    let n = better + worse;
+    // exact binomial distribution, the terms are summed as logarithms to avoid underflow
+    let mut ln_choose = 0.0;
+    let mut tail = 0.0;
+    for k in 0..=better.min(worse) {
+        if 0 < k {
+            ln_choose += ((n - k + 1) as f64).ln() - (k as f64).ln();
+        }
+        tail += (ln_choose - n as f64 * 2f64.ln()).exp();
+    }
+    SignTest {
+        better,
+        worse,
+        p_value: (2.0 * tail).min(1.0),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tournament {
+    seed: u64,
+    limit: usize,
+    secrets: Vec<Values>,
+    entries: Vec<Entry>,
+}
+
+impl Tournament {
+    // every solver plays every secret, a failing bot stops the tournament
+    pub fn run(
+        seed: u64,
+        count: usize,
+        limit: usize,
+        solvers: &[(String, Solver)],
+    ) -> Result<Self, Error> {
+        let secrets = pick_secrets(seed, count);
+        let mut entries = Vec::new();
+        for (name, solver) in solvers.iter() {
+            let mut guesses = Vec::new();
+            for secret in secrets.iter() {
+                let mut mm = Mastermind::new_with_state(*secret);
+                mm.set_verbose(false);
+                solver
+                    .solve(&mut mm)
+                    .map_err(|error| Error::new(error.kind(), format!("{}: {}", name, error)))?;
+                guesses.push(mm.get_guesses().len());
+            }
+            entries.push(Entry {
+                name: name.clone(),
+                guesses,
+            });
+        }
+        Ok(Tournament {
+            seed,
+            limit,
+            secrets,
+            entries,
+        })
+    }
+
+    // every secret is a game between every two solvers, fewer guesses win
+    pub fn ratings(&self) -> Vec<f64> {
+        let mut ratings = vec![INITIAL_RATING; self.entries.len()];
+        for secret in 0..self.secrets.len() {
+            for i in 0..self.entries.len() {
+                for j in i + 1..self.entries.len() {
+                    let (a, b) = (
+                        self.entries[i].guesses[secret],
+                        self.entries[j].guesses[secret],
+                    );
+                    let score = match a.cmp(&b) {
+                        std::cmp::Ordering::Less => 1.0,
+                        std::cmp::Ordering::Equal => 0.5,
+                        std::cmp::Ordering::Greater => 0.0,
+                    };
+                    let expected = 1.0 / (1.0 + 10f64.powf((ratings[j] - ratings[i]) / 400.0));
+                    ratings[i] += RATING_FACTOR * (score - expected);
+                    ratings[j] -= RATING_FACTOR * (score - expected);
+                }
+            }
+        }
+        ratings
+    }
+
+    // best first, by average and then by worst case
+    pub fn leaderboard(&self) -> Vec<(&Entry, f64)> {
+        let mut board: Vec<(&Entry, f64)> = self.entries.iter().zip(self.ratings()).collect();
+        board.sort_by(|(a, _), (b, _)| {
+            a.average()
+                .total_cmp(&b.average())
+                .then(a.worst_case().cmp(&b.worst_case()))
+        });
+        board
+    }
+
+    pub fn show_leaderboard(&self) -> String {
+        let mut text = String::new();
+        writeln!(
+            text,
+            "{} secrets with seed {}, a win needs at most {} guesses",
+            self.secrets.len(),
+            self.seed,
+            self.limit
+        )
+        .unwrap();
+        writeln!(text, "rank  solver      average  worst  win rate  rating").unwrap();
+        let board = self.leaderboard();
+        for (rank, (entry, rating)) in board.iter().enumerate() {
+            writeln!(
+                text,
+                "{:4}  {:10}  {:7.3}  {:5}  {:7.1}%  {:6.0}",
+                rank + 1,
+                entry.name,
+                entry.average(),
+                entry.worst_case(),
+                100.0 * entry.win_rate(self.limit),
+                rating
+            )
+            .unwrap();
+        }
+        for (i, (first, _)) in board.iter().enumerate() {
+            for (second, _) in board.iter().skip(i + 1) {
+                let test = sign_test(&first.guesses, &second.guesses);
+                writeln!(
+                    text,
+                    "{} vs {}: {} better, {} worse, p = {:.4}{}",
+                    first.name,
+                    second.name,
+                    test.better,
+                    test.worse,
+                    test.p_value,
+                    if test.p_value < SIGNIFICANCE {
+                        " (significant)"
+                    } else {
+                        ""
+                    }
+                )
+                .unwrap();
+            }
+        }
+        text
+    }
+
+    // the regressions of the new results against the old ones and a report of every solver
+    pub fn compare(old: &Tournament, new: &Tournament) -> Result<(usize, String), String> {
+        if old.secrets != new.secrets {
+            return Err(String::from(
+                "the tournaments were played with other secrets",
+            ));
+        }
+        let mut regressions = 0;
+        let mut text = String::new();
+        for entry in new.entries.iter() {
+            let previous = match old.entries.iter().find(|e| e.name == entry.name) {
+                Some(previous) => previous,
+                None => {
+                    writeln!(text, "{}: new solver", entry.name).unwrap();
+                    continue;
+                }
+            };
+            let test = sign_test(&entry.guesses, &previous.guesses);
+            let regression = previous.average() < entry.average() && test.p_value < SIGNIFICANCE;
+            if regression {
+                regressions += 1;
+            }
+            writeln!(
+                text,
+                "{}: average {:.3} -> {:.3}, {} better, {} worse, p = {:.4}{}",
+                entry.name,
+                previous.average(),
+                entry.average(),
+                test.better,
+                test.worse,
+                test.p_value,
+                if regression { " REGRESSION" } else { "" }
+            )
+            .unwrap();
+        }
+        Ok((regressions, text))
+    }
+}
+
+// the results file
+impl Display for Tournament {
+    fn fmt(&self, format: &mut Formatter) -> Result<(), FmtError> {
+        writeln!(format, "# mastermind tournament results")?;
+        writeln!(format, "seed {}", self.seed)?;
+        writeln!(format, "limit {}", self.limit)?;
+        write!(format, "secrets")?;
+        for secret in self.secrets.iter() {
+            write!(format, " {}", get_string_from_guess(secret))?;
+        }
+        writeln!(format)?;
+        for entry in self.entries.iter() {
+            write!(format, "solver {}", entry.name)?;
+            for guesses in entry.guesses.iter() {
+                write!(format, " {}", guesses)?;
+            }
+            writeln!(format)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_results_line(tournament: &mut Tournament, line: &str) -> Option<()> {
+    let mut words = line.split_whitespace();
+    match words.next()? {
+        "seed" => tournament.seed = words.next()?.parse().ok()?,
+        "limit" => tournament.limit = words.next()?.parse().ok()?,
+        "secrets" => {
+            tournament.secrets = words.map(parse_guess).collect::<Option<Vec<Values>>>()?
+        }
+        "solver" => {
+            let name = String::from(words.next()?);
+            let guesses = words
+                .map(|word| word.parse::<usize>().ok())
+                .collect::<Option<Vec<usize>>>()?;
+            if guesses.len() != tournament.secrets.len() {
+                return None;
+            }
+            tournament.entries.push(Entry { name, guesses });
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+// empty lines and lines starting with '#' are skipped
+pub fn read_results<T: BufRead>(input: T) -> Result<Tournament, Error> {
+    let mut tournament = Tournament {
+        seed: 0,
+        limit: 0,
+        secrets: Vec::new(),
+        entries: Vec::new(),
+    };
+    for (number, line) in input.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if parse_results_line(&mut tournament, line).is_none() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid results in line {}", number + 1),
+            ));
+        }
+    }
+    Ok(tournament)
+}
+
+pub fn load_results(path: &str) -> Result<Tournament, Error> {
+    read_results(BufReader::new(File::open(path)?))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::knuth_solver;
+    use crate::single_digit_solver;
+    use crate::solver::Solver;
+    use crate::state_of_the_art_solver;
+    use crate::tournament::{pick_secrets, read_results, sign_test, Entry, Tournament};
+    use std::io::ErrorKind;
+
+    fn solvers() -> Vec<(String, Solver)> {
+        vec![
+            (
+                String::from("single"),
+                Solver::Builtin(single_digit_solver::solve),
+            ),
+            (String::from("knuth"), Solver::Builtin(knuth_solver::solve)),
+            (
+                String::from("state"),
+                Solver::Builtin(state_of_the_art_solver::solve),
+            ),
+        ]
+    }
+
+    #[test]
+    fn secrets_depend_on_the_seed() {
+        assert_eq!(pick_secrets(7, 20), pick_secrets(7, 20));
+        assert_ne!(pick_secrets(7, 20), pick_secrets(8, 20));
+    }
+
+    #[test]
+    fn sign_test_matches_binomial_distribution() {
+        // 5 of 5 better: 2 * 0.5^5
+        let test = sign_test(&[1, 1, 1, 1, 1, 3], &[2, 2, 2, 2, 2, 3]);
+        assert_eq!((5, 0), (test.better, test.worse));
+        assert!((test.p_value - 0.0625).abs() < 1e-12);
+        // 1 of 4 better: 2 * (1 + 4) / 16
+        let test = sign_test(&[1, 3, 3, 3], &[2, 2, 2, 2]);
+        assert!((test.p_value - 0.625).abs() < 1e-12);
+        assert_eq!(1.0, sign_test(&[1, 2], &[2, 1]).p_value);
+        assert_eq!(1.0, sign_test(&[2], &[2]).p_value);
+        // many secrets do not underflow
+        let test = sign_test(&vec![1; 2000], &vec![2; 2000]);
+        assert!(test.p_value < 1e-100);
+    }
+
+    #[test]
+    fn entry_statistics() {
+        let entry = Entry {
+            name: String::from("x"),
+            guesses: vec![3, 5, 11, 5],
+        };
+        assert_eq!(6.0, entry.average());
+        assert_eq!(11, entry.worst_case());
+        assert_eq!(0.75, entry.win_rate(10));
+    }
+
+    #[test]
+    fn leaderboard_ranks_the_solvers() {
+        let tournament = Tournament::run(3, 30, 6, &solvers()).unwrap();
+        let board = tournament.leaderboard();
+        assert_eq!("single", board[2].0.name);
+        assert!(board[2].1 < board[0].1 && board[2].1 < board[1].1);
+        let text = tournament.show_leaderboard();
+        assert!(text.contains("single vs knuth") || text.contains("knuth vs single"));
+        assert!(text.contains("(significant)"));
+    }
+
+    #[test]
+    fn results_file_survives_writing_and_reading() {
+        let tournament = Tournament::run(5, 10, 10, &solvers()).unwrap();
+        let read = read_results(tournament.to_string().as_bytes()).unwrap();
+        assert_eq!(tournament, read);
+    }
+
+    #[test]
+    fn read_results_reports_invalid_line() {
+        let error = read_results("seed 1\nsecrets 0123\nsolver x 1 2\n".as_bytes()).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, error.kind());
+        assert!(error.to_string().contains("line 3"));
+    }
+
+    #[test]
+    fn compare_finds_regressions() {
+        let old = Tournament::run(9, 40, 10, &solvers()[1..]).unwrap();
+        let (regressions, _) = Tournament::compare(&old, &old).unwrap();
+        assert_eq!(0, regressions);
+
+        // knuth got replaced by a worse solver
+        let mut new = old.clone();
+        new.entries[0].guesses = Tournament::run(9, 40, 10, &solvers()[..1]).unwrap().entries[0]
+            .guesses
+            .clone();
+        let (regressions, text) = Tournament::compare(&old, &new).unwrap();
+        assert_eq!(1, regressions);
+        assert!(text.contains("knuth: average"));
+        assert!(text.contains("REGRESSION"));
+
+        let other = Tournament::run(10, 40, 10, &solvers()[1..]).unwrap();
+        assert!(Tournament::compare(&old, &other).is_err());
+    }
+
+    #[test]
+    fn failing_bot_stops_the_tournament() {
+        let solvers = [(
+            String::from("dead"),
+            Solver::Bot(vec![String::from("/nonexistent/bot")]),
+        )];
+        let error = Tournament::run(1, 2, 10, &solvers).unwrap_err();
+        assert!(error.to_string().starts_with("dead: "));
+    }
+}
@@ -10,6 +10,13 @@ pub const WHITE: &str = "\x1B[37m";
 pub const RESET: &str = "\x1B[0m";
 // moves one line up
 pub const CURSOR_UP: &str = "\x1B[1A";
+// erases the current line
+pub const CLEAR_LINE: &str = "\x1B[2K";
+// remembers the cursor position and goes back to it
+pub const SAVE_CURSOR: &str = "\x1B7";
+pub const RESTORE_CURSOR: &str = "\x1B8";
+// erases the terminal and moves to its top left corner
+pub const CLEAR_SCREEN: &str = "\x1B[2J\x1B[H";
 
 pub const DOTS: &str = "▉▉▉▉";
 pub const CHAR: &str = "▉";
@@ -29,9 +36,36 @@ pub fn get_random_number_u8(max: u8) -> u8 {
     (get_random_number() % u64::from(max)) as u8
 }
 
+pub fn get_random_number_usize(max: usize) -> usize {
+    (get_random_number() % max as u64) as usize
+}
+
+// reproducible random numbers, the same seed always gives the same sequence (splitmix64)
+pub struct SeededRandom {
+    state: u64,
+}
+
+impl SeededRandom {
+    pub fn new(seed: u64) -> Self {
+        SeededRandom { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_usize(&mut self, max: usize) -> usize {
+        (self.next_u64() % max as u64) as usize
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::util::get_random_number_u8;
+    use crate::util::{get_random_number_u8, get_random_number_usize, SeededRandom};
 
     #[test]
     fn random_number_generator_u8_with_valid_limits() {
@@ -59,4 +93,34 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn random_number_generator_usize_exceeds_u8() {
+        let upper_limit = 1296;
+        let mut generated = false;
+        while !generated {
+            let x = get_random_number_usize(upper_limit);
+            assert!(x < upper_limit);
+            generated = x > 255;
+        }
+    }
+
+    #[test]
+    fn seeded_random_repeats_the_sequence() {
+        let mut first = SeededRandom::new(42);
+        let mut second = SeededRandom::new(42);
+        let mut other = SeededRandom::new(43);
+        let numbers: Vec<u64> = (0..10).map(|_| first.next_u64()).collect();
+        assert_eq!(
+            numbers,
+            (0..10).map(|_| second.next_u64()).collect::<Vec<_>>()
+        );
+        assert_ne!(
+            numbers,
+            (0..10).map(|_| other.next_u64()).collect::<Vec<_>>()
+        );
+        for _ in 0..100 {
+            assert!(first.next_usize(1296) < 1296);
+        }
+    }
 }
@@ -0,0 +1,59 @@
+use crate::mastermind_state::NUM_ELEMENTS;
+use crate::util::{CHAR, GREEN, RESET, WHITE, YELLOW};
+use std::fmt::{Display, Error, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Marker {
+    Correct,
+    Present,
+    Absent,
+}
+
+impl Marker {
+    fn to_shell_escape(self) -> &'static str {
+        match self {
+            Marker::Correct => GREEN,
+            Marker::Present => YELLOW,
+            Marker::Absent => WHITE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WordleEvaluation {
+    markers: [Marker; NUM_ELEMENTS],
+}
+
+impl WordleEvaluation {
+    pub fn new(markers: [Marker; NUM_ELEMENTS]) -> Self {
+        WordleEvaluation { markers }
+    }
+}
+
+impl Display for WordleEvaluation {
+    fn fmt(&self, format: &mut Formatter) -> Result<(), Error> {
+        for marker in self.markers.iter() {
+            write!(format, "{}{}", marker.to_shell_escape(), CHAR)?;
+        }
+        write!(format, "{}", RESET)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::wordle_evaluation::{Marker, WordleEvaluation};
+
+    #[test]
+    fn display() {
+        let eval = WordleEvaluation::new([
+            Marker::Correct,
+            Marker::Present,
+            Marker::Absent,
+            Marker::Correct,
+        ]);
+        assert_eq!(
+            "\u{1b}[32m▉\u{1b}[33m▉\u{1b}[37m▉\u{1b}[32m▉\u{1b}[0m",
+            format!("{}", eval)
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use std::process::Command;
+
+#[test]
+fn engine_plays_against_the_reference_bot() {
+    let binary = env!("CARGO_BIN_EXE_mastermind");
+    let output = Command::new(binary)
+        .args(["bot", binary, "reference-bot"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Game solved in"));
+}
+
+#[test]
+fn failing_bot_is_reported() {
+    let output = Command::new(env!("CARGO_BIN_EXE_mastermind"))
+        .args(["bot", "/nonexistent/bot"])
+        .output()
+        .unwrap();
+    assert_eq!(Some(1), output.status.code());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("bot failed: "));
+    assert!(!stderr.contains("panicked"));
+}
+
+#[test]
+fn bot_plays_in_the_tournament() {
+    let binary = env!("CARGO_BIN_EXE_mastermind");
+    let bot = format!("bot:{} reference-bot", binary);
+    let output = Command::new(binary)
+        .args(["tournament", "secrets=5", "state", &bot])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // the reference bot plays the state solver
+    assert!(stdout.contains(&format!("state vs {}: 0 better, 0 worse", bot)));
+}
+
+#[test]
+fn failing_bot_stops_the_tournament() {
+    let output = Command::new(env!("CARGO_BIN_EXE_mastermind"))
+        .args(["tournament", "secrets=2", "bot:/nonexistent/bot"])
+        .output()
+        .unwrap();
+    assert_eq!(Some(1), output.status.code());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("bot:/nonexistent/bot: "));
+    assert!(!stderr.contains("panicked"));
+}
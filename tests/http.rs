@@ -0,0 +1,122 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+
+// the server is killed when the test ends, also when it fails
+struct Server {
+    child: Child,
+    address: String,
+}
+
+impl Server {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_mastermind"))
+            .args(["http", "127.0.0.1:0"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut line = String::new();
+        BufReader::new(child.stdout.as_mut().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let address = line
+            .trim()
+            .strip_prefix("listening on ")
+            .unwrap()
+            .to_string();
+        Server { child, address }
+    }
+
+    fn request(&self, method: &str, path: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(&self.address).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        assert!(head.contains("Content-Type: application/json"));
+        (status, body.to_string())
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn json_value<'a>(body: &'a str, key: &str) -> &'a str {
+    let key = format!("\"{}\":", key);
+    let rest = &body[body.find(&key).unwrap() + key.len()..];
+    let end = rest.find([',', '}']).unwrap();
+    rest[..end].trim_matches('"')
+}
+
+#[test]
+fn solve_a_game_with_hints() {
+    let server = Server::start();
+    let (status, body) = server.request("POST", "/games", "");
+    assert_eq!(201, status);
+    let id = json_value(&body, "id").to_string();
+
+    // knuth_solver needs at most five guesses
+    let mut solved = false;
+    for guesses in 1..=5 {
+        let (status, hint) = server.request("GET", &format!("/games/{}/hint", id), "");
+        assert_eq!(200, status);
+        let guess = json_value(&hint, "hint");
+        let (status, body) = server.request(
+            "POST",
+            &format!("/games/{}/guesses", id),
+            &format!("{{\"guess\":\"{}\"}}", guess),
+        );
+        assert_eq!(200, status);
+        assert_eq!(guesses.to_string(), json_value(&body, "guesses"));
+        if "true" == json_value(&body, "solved") {
+            solved = true;
+            break;
+        }
+    }
+    assert!(solved);
+
+    let (status, body) = server.request("GET", &format!("/games/{}", id), "");
+    assert_eq!(200, status);
+    assert!(body.contains("\"solved\":true"));
+    assert!(body.contains("\"correct_match\":4"));
+}
+
+#[test]
+fn games_are_separate() {
+    let server = Server::start();
+    let (_, first) = server.request("POST", "/games", "");
+    let (_, second) = server.request("POST", "/games", "");
+    assert_ne!(json_value(&first, "id"), json_value(&second, "id"));
+
+    let path = format!("/games/{}/guesses", json_value(&first, "id"));
+    let (status, _) = server.request("POST", &path, "{\"guess\":\"0011\"}");
+    assert_eq!(200, status);
+    let (_, body) = server.request("GET", &format!("/games/{}", json_value(&second, "id")), "");
+    assert!(body.ends_with("\"guesses\":[]}"));
+}
+
+#[test]
+fn errors_are_json() {
+    let server = Server::start();
+    let (status, body) = server.request("GET", "/games/42", "");
+    assert_eq!(404, status);
+    assert_eq!("{\"error\":\"no such game\"}", body);
+    let (_, game) = server.request("POST", "/games", "");
+    let path = format!("/games/{}/guesses", json_value(&game, "id"));
+    let (status, body) = server.request("POST", &path, "{\"guess\":\"red\"}");
+    assert_eq!(400, status);
+    assert!(body.starts_with("{\"error\":"));
+}